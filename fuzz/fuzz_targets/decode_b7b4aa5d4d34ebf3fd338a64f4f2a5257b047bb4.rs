@@ -0,0 +1,15 @@
+#![no_main]
+
+use demo_serde_txmeta::legacy::b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4::{
+    from_bincode, to_bincode,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(meta) = from_bincode(data) {
+        let encoded = to_bincode(&meta).unwrap();
+        // bincode leaves trailing bytes unread.
+        assert!(data.starts_with(&encoded));
+        assert_eq!(from_bincode(&encoded).unwrap(), meta);
+    }
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use demo_serde_txmeta::legacy::ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb::{
+    from_bincode, to_bincode,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(meta) = from_bincode(data) {
+        let encoded = to_bincode(&meta).unwrap();
+        // bincode leaves trailing bytes unread, and default_on_eof reads a
+        // missing or truncated inner_instructions as None, which encodes as
+        // one 0 byte.
+        let defaulted =
+            meta.inner_instructions.is_none() && data.starts_with(&encoded[..encoded.len() - 1]);
+        assert!(data.starts_with(&encoded) || defaulted);
+        assert_eq!(from_bincode(&encoded).unwrap(), meta);
+    }
+});
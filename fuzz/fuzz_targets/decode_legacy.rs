@@ -0,0 +1,22 @@
+#![no_main]
+
+use demo_serde_txmeta::legacy::{decode, TransactionStatusMeta};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(meta) = decode(data) {
+        let encoded = meta.to_bincode().unwrap();
+        match &meta {
+            // Only picked when the blob ends right after post_balances.
+            TransactionStatusMeta::B7b4(_) => assert_eq!(encoded, data),
+            // default_on_eof reads a missing or truncated inner_instructions
+            // as None, written back as one 0 byte.
+            TransactionStatusMeta::Ce598(meta) => {
+                let defaulted = meta.inner_instructions.is_none()
+                    && data.starts_with(&encoded[..encoded.len() - 1]);
+                assert!(data.starts_with(&encoded) || defaulted);
+            }
+        }
+        assert_eq!(decode(&encoded).unwrap(), meta);
+    }
+});
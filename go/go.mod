@@ -0,0 +1,3 @@
+module github.com/gagliardetto/solana-tx-meta-parser-generators/go
+
+go 1.14
@@ -0,0 +1,95 @@
+// Package meta decodes TransactionStatusMeta blobs in any known encoding.
+package meta
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/gagliardetto/solana-tx-meta-parser-generators/go/parse_legacy_transaction_status_meta_b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4"
+	"github.com/gagliardetto/solana-tx-meta-parser-generators/go/parse_legacy_transaction_status_meta_ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb"
+)
+
+// Encoding is how a meta was written.
+type Encoding string
+
+const (
+	Protobuf Encoding = "protobuf"
+	Bincode  Encoding = "bincode"
+)
+
+// Current is the version of metas in the layouts of solana-storage-proto.
+const Current = "current"
+
+// Decoded is a meta along with how it was written.
+type Decoded struct {
+	Encoding Encoding
+	// Version is Current or the commit of a legacy layout.
+	Version string
+	// Meta is what a Chain function returned, or a pointer to the
+	// TransactionStatusMeta of the legacy layout.
+	Meta interface{}
+}
+
+// Chain decodes metas as protobuf first, then as bincode, the same way as
+// the Rust decoder. The current layouts come from Solana's own bindings and
+// are left to the caller; nil functions are skipped. Like the Rust decoder,
+// Protobuf should fail on metas without balances, which are short blobs of
+// another encoding.
+//
+// The legacy layouts are prefixes of the current bincode one, which reads
+// them too. They only tell the version of what it decoded: a blob that is
+// exactly one of them was written with it. Without a Bincode function they
+// decode the blob themselves.
+type Chain struct {
+	Protobuf func(data []byte) (interface{}, error)
+	Bincode  func(data []byte) (interface{}, error)
+}
+
+func (c Chain) Decode(data []byte) (*Decoded, error) {
+	if len(data) == 0 {
+		return nil, fmt.Errorf("empty blob")
+	}
+	var failures []string
+	if c.Protobuf != nil {
+		meta, err := c.Protobuf(data)
+		if err == nil {
+			return &Decoded{Encoding: Protobuf, Version: Current, Meta: meta}, nil
+		}
+		failures = append(failures, fmt.Sprintf("protobuf: %v", err))
+	}
+	legacy, legacyFailures := decodeLegacy(data)
+	if c.Bincode == nil {
+		if legacy != nil {
+			return legacy, nil
+		}
+		failures = append(failures, legacyFailures...)
+		return nil, fmt.Errorf("no encoding matches: %s", strings.Join(failures, "; "))
+	}
+	meta, err := c.Bincode(data)
+	if err != nil {
+		failures = append(failures, fmt.Sprintf("bincode: %v", err))
+		return nil, fmt.Errorf("no encoding matches: %s", strings.Join(failures, "; "))
+	}
+	version := Current
+	if legacy != nil {
+		version = legacy.Version
+	}
+	return &Decoded{Encoding: Bincode, Version: version, Meta: meta}, nil
+}
+
+// decodeLegacy decodes data with the first legacy layout it is exactly, oldest
+// first, or returns why none matches.
+func decodeLegacy(data []byte) (*Decoded, []string) {
+	var failures []string
+	if meta, err := parse_legacy_transaction_status_meta_b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4.BincodeDeserializeTransactionStatusMeta(data); err == nil {
+		return &Decoded{Encoding: Bincode, Version: "b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4", Meta: &meta}, nil
+	} else {
+		failures = append(failures, fmt.Sprintf("b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4: %v", err))
+	}
+	if meta, err := parse_legacy_transaction_status_meta_ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb.BincodeDeserializeTransactionStatusMeta(data); err == nil {
+		return &Decoded{Encoding: Bincode, Version: "ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb", Meta: &meta}, nil
+	} else {
+		failures = append(failures, fmt.Sprintf("ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb: %v", err))
+	}
+	return nil, failures
+}
@@ -3,8 +3,8 @@ package parse_legacy_transaction_status_meta_b7b4aa5d4d34ebf3fd338a64f4f2a5257b0
 
 import (
 	"fmt"
-	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
-	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/bincode"
+	"github.com/gagliardetto/solana-tx-meta-parser-generators/go/serde"
+	"github.com/gagliardetto/solana-tx-meta-parser-generators/go/bincode"
 )
 
 
@@ -218,7 +218,11 @@ func BincodeDeserializeInstructionError(input []byte) (InstructionError, error)
 		var obj InstructionError
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj InstructionError
+		return obj, err
+	}
 	obj, err := DeserializeInstructionError(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -1018,7 +1022,11 @@ func BincodeDeserializeResult(input []byte) (Result, error) {
 		var obj Result
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj Result
+		return obj, err
+	}
 	obj, err := DeserializeResult(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -1205,7 +1213,11 @@ func BincodeDeserializeTransactionError(input []byte) (TransactionError, error)
 		var obj TransactionError
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj TransactionError
+		return obj, err
+	}
 	obj, err := DeserializeTransactionError(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -1653,7 +1665,11 @@ func BincodeDeserializeTransactionStatusMeta(input []byte) (TransactionStatusMet
 		var obj TransactionStatusMeta
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj TransactionStatusMeta
+		return obj, err
+	}
 	obj, err := DeserializeTransactionStatusMeta(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -0,0 +1,108 @@
+package parse_legacy_transaction_status_meta_b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4
+
+import (
+	"fmt"
+	"math"
+
+	"github.com/gagliardetto/solana-tx-meta-parser-generators/go/serde"
+)
+
+// DecodeLimits bounds the work and memory spent decoding a single blob.
+type DecodeLimits struct {
+	// MaxSequenceLength bounds length prefixes, checked before allocating.
+	MaxSequenceLength uint64
+	// MaxTotalBytes bounds the size of the whole blob, trailing bytes included.
+	MaxTotalBytes uint64
+	// MaxContainerDepth bounds the nesting of structs and enum values, each
+	// counting one level. Sequences, tuples and options do not count, as in the
+	// Rust decoder.
+	MaxContainerDepth uint64
+}
+
+// DefaultDecodeLimits are the limits of the BincodeDeserialize functions.
+var DefaultDecodeLimits = DecodeLimits{
+	MaxSequenceLength: 65536,
+	MaxTotalBytes:     16777216,
+	MaxContainerDepth: 32,
+}
+
+// LimitError reports the limit a blob went over.
+type LimitError struct {
+	Limit string
+	Value uint64
+	Max   uint64
+}
+
+func (e *LimitError) Error() string {
+	return fmt.Sprintf("decode limit exceeded: %s of %d is over the limit of %d", e.Limit, e.Value, e.Max)
+}
+
+// limitedDeserializer is the runtime's Bincode deserializer with limits.
+type limitedDeserializer struct {
+	serde.BinaryDeserializer
+	limits DecodeLimits
+}
+
+// NewLimitedDeserializer returns a Bincode deserializer for input within limits,
+// e.g. for DeserializeTransactionStatusMeta, or a *LimitError when input is
+// already over MaxTotalBytes.
+func NewLimitedDeserializer(input []byte, limits DecodeLimits) (serde.Deserializer, error) {
+	if size := uint64(len(input)); size > limits.MaxTotalBytes {
+		return nil, &LimitError{"blob size", size, limits.MaxTotalBytes}
+	}
+	return &limitedDeserializer{*serde.NewBinaryDeserializer(input, limits.MaxContainerDepth), limits}, nil
+}
+
+// checkLength fails on a sequence longer than MaxSequenceLength, before it is allocated.
+func (d *limitedDeserializer) checkLength(length uint64) error {
+	if length > d.limits.MaxSequenceLength {
+		return &LimitError{"sequence length", length, d.limits.MaxSequenceLength}
+	}
+	return nil
+}
+
+func (d *limitedDeserializer) IncreaseContainerDepth() error {
+	if d.BinaryDeserializer.IncreaseContainerDepth() != nil {
+		return &LimitError{"container depth", d.limits.MaxContainerDepth + 1, d.limits.MaxContainerDepth}
+	}
+	return nil
+}
+
+func (d *limitedDeserializer) DeserializeLen() (uint64, error) {
+	length, err := d.DeserializeU64()
+	if err != nil {
+		return 0, err
+	}
+	if err := d.checkLength(length); err != nil {
+		return 0, err
+	}
+	return length, nil
+}
+
+// The remaining methods are those of the runtime's Bincode deserializer.
+
+func (d *limitedDeserializer) DeserializeF32() (float32, error) {
+	ret, err := d.DeserializeU32()
+	return math.Float32frombits(ret), err
+}
+
+func (d *limitedDeserializer) DeserializeF64() (float64, error) {
+	ret, err := d.DeserializeU64()
+	return math.Float64frombits(ret), err
+}
+
+func (d *limitedDeserializer) DeserializeBytes() ([]byte, error) {
+	return d.BinaryDeserializer.DeserializeBytes(d.DeserializeLen)
+}
+
+func (d *limitedDeserializer) DeserializeStr() (string, error) {
+	return d.BinaryDeserializer.DeserializeStr(d.DeserializeLen)
+}
+
+func (d *limitedDeserializer) DeserializeVariantIndex() (uint32, error) {
+	return d.DeserializeU32()
+}
+
+func (d *limitedDeserializer) CheckThatKeySlicesAreIncreasing(key1, key2 serde.Slice) error {
+	return nil
+}
@@ -0,0 +1,375 @@
+package parse_legacy_transaction_status_meta_b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4
+
+import (
+	"bytes"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"reflect"
+	"testing"
+)
+
+// Vectors produced by the Rust definitions of b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4.
+func TestGoldenVectors(t *testing.T) {
+	vectors := []struct {
+		name     string
+		bincode  string
+		expected TransactionStatusMeta
+		rpc      string
+		depth    uint64
+	}{
+		{
+			name:     "Ok",
+			bincode:  "00000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Ok{}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":null,\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Ok\":null}}",
+			depth:    2,
+		},
+		{
+			name:     "Err/AccountInUse",
+			bincode:  "0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__AccountInUse{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"AccountInUse\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"AccountInUse\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/AccountLoadedTwice",
+			bincode:  "0100000001000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__AccountLoadedTwice{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"AccountLoadedTwice\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"AccountLoadedTwice\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/AccountNotFound",
+			bincode:  "0100000002000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__AccountNotFound{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"AccountNotFound\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"AccountNotFound\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/ProgramAccountNotFound",
+			bincode:  "0100000003000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__ProgramAccountNotFound{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"ProgramAccountNotFound\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"ProgramAccountNotFound\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/InsufficientFundsForFee",
+			bincode:  "0100000004000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InsufficientFundsForFee{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"InsufficientFundsForFee\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"InsufficientFundsForFee\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/InvalidAccountForFee",
+			bincode:  "0100000005000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InvalidAccountForFee{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"InvalidAccountForFee\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"InvalidAccountForFee\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/DuplicateSignature",
+			bincode:  "0100000006000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__DuplicateSignature{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"DuplicateSignature\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"DuplicateSignature\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/BlockhashNotFound",
+			bincode:  "0100000007000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__BlockhashNotFound{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"BlockhashNotFound\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"BlockhashNotFound\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/InstructionError/GenericError",
+			bincode:  "0100000008000000ff00000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__GenericError{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"GenericError\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"GenericError\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/InvalidArgument",
+			bincode:  "0100000008000000ff01000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__InvalidArgument{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"InvalidArgument\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"InvalidArgument\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/InvalidInstructionData",
+			bincode:  "0100000008000000ff02000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__InvalidInstructionData{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"InvalidInstructionData\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"InvalidInstructionData\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/InvalidAccountData",
+			bincode:  "0100000008000000ff03000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__InvalidAccountData{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"InvalidAccountData\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"InvalidAccountData\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/AccountDataTooSmall",
+			bincode:  "0100000008000000ff04000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__AccountDataTooSmall{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"AccountDataTooSmall\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"AccountDataTooSmall\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/InsufficientFunds",
+			bincode:  "0100000008000000ff05000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__InsufficientFunds{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"InsufficientFunds\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"InsufficientFunds\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/IncorrectProgramId",
+			bincode:  "0100000008000000ff06000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__IncorrectProgramId{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"IncorrectProgramId\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"IncorrectProgramId\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/MissingRequiredSignature",
+			bincode:  "0100000008000000ff07000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__MissingRequiredSignature{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"MissingRequiredSignature\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"MissingRequiredSignature\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/AccountAlreadyInitialized",
+			bincode:  "0100000008000000ff08000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__AccountAlreadyInitialized{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"AccountAlreadyInitialized\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"AccountAlreadyInitialized\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/UninitializedAccount",
+			bincode:  "0100000008000000ff09000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__UninitializedAccount{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"UninitializedAccount\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"UninitializedAccount\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/UnbalancedInstruction",
+			bincode:  "0100000008000000ff0a000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__UnbalancedInstruction{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"UnbalancedInstruction\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"UnbalancedInstruction\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/ModifiedProgramId",
+			bincode:  "0100000008000000ff0b000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__ModifiedProgramId{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"ModifiedProgramId\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"ModifiedProgramId\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/ExternalAccountLamportSpend",
+			bincode:  "0100000008000000ff0c000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__ExternalAccountLamportSpend{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"ExternalAccountLamportSpend\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"ExternalAccountLamportSpend\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/ExternalAccountDataModified",
+			bincode:  "0100000008000000ff0d000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__ExternalAccountDataModified{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"ExternalAccountDataModified\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"ExternalAccountDataModified\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/ReadonlyLamportChange",
+			bincode:  "0100000008000000ff0e000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__ReadonlyLamportChange{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"ReadonlyLamportChange\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"ReadonlyLamportChange\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/ReadonlyDataModified",
+			bincode:  "0100000008000000ff0f000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__ReadonlyDataModified{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"ReadonlyDataModified\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"ReadonlyDataModified\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/DuplicateAccountIndex",
+			bincode:  "0100000008000000ff10000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__DuplicateAccountIndex{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"DuplicateAccountIndex\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"DuplicateAccountIndex\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/ExecutableModified",
+			bincode:  "0100000008000000ff11000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__ExecutableModified{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"ExecutableModified\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"ExecutableModified\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/RentEpochModified",
+			bincode:  "0100000008000000ff12000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__RentEpochModified{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"RentEpochModified\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"RentEpochModified\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/NotEnoughAccountKeys",
+			bincode:  "0100000008000000ff13000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__NotEnoughAccountKeys{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"NotEnoughAccountKeys\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"NotEnoughAccountKeys\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/AccountDataSizeChanged",
+			bincode:  "0100000008000000ff14000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__AccountDataSizeChanged{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"AccountDataSizeChanged\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"AccountDataSizeChanged\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/AccountNotExecutable",
+			bincode:  "0100000008000000ff15000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__AccountNotExecutable{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"AccountNotExecutable\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"AccountNotExecutable\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/AccountBorrowFailed",
+			bincode:  "0100000008000000ff16000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__AccountBorrowFailed{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"AccountBorrowFailed\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"AccountBorrowFailed\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/AccountBorrowOutstanding",
+			bincode:  "0100000008000000ff17000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__AccountBorrowOutstanding{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"AccountBorrowOutstanding\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"AccountBorrowOutstanding\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/DuplicateAccountOutOfSync",
+			bincode:  "0100000008000000ff18000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__DuplicateAccountOutOfSync{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"DuplicateAccountOutOfSync\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"DuplicateAccountOutOfSync\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/CustomError",
+			bincode:  "0100000008000000ff19000000ffffffffffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: func() *InstructionError__CustomError { v := (InstructionError__CustomError)(4294967295); return &v }()}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,{\"CustomError\":4294967295}]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,{\"CustomError\":4294967295}]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/InstructionError/InvalidError",
+			bincode:  "0100000008000000ff1a000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InstructionError{Field0: 255, Field1: &InstructionError__InvalidError{}}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":{\"InstructionError\":[255,\"InvalidError\"]},\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":{\"InstructionError\":[255,\"InvalidError\"]}}}",
+			depth:    4,
+		},
+		{
+			name:     "Err/CallChainTooDeep",
+			bincode:  "0100000009000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__CallChainTooDeep{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"CallChainTooDeep\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"CallChainTooDeep\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/MissingSignatureForFee",
+			bincode:  "010000000a000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__MissingSignatureForFee{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"MissingSignatureForFee\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"MissingSignatureForFee\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/InvalidAccountIndex",
+			bincode:  "010000000b000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InvalidAccountIndex{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"InvalidAccountIndex\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"InvalidAccountIndex\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/SignatureFailure",
+			bincode:  "010000000c000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__SignatureFailure{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"SignatureFailure\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"SignatureFailure\"}}",
+			depth:    3,
+		},
+		{
+			name:     "Err/InvalidProgramForExecution",
+			bincode:  "010000000d000000ffffffffffffffff0100000000000000ffffffffffffffff0100000000000000ffffffffffffffff",
+			expected: TransactionStatusMeta{Status: &Result__Err{Value: &TransactionError__InvalidProgramForExecution{}}, Fee: 18446744073709551615, PreBalances: []uint64{18446744073709551615}, PostBalances: []uint64{18446744073709551615}},
+			rpc:      "{\"err\":\"InvalidProgramForExecution\",\"fee\":18446744073709551615,\"postBalances\":[18446744073709551615],\"preBalances\":[18446744073709551615],\"status\":{\"Err\":\"InvalidProgramForExecution\"}}",
+			depth:    3,
+		},
+	}
+	for _, vector := range vectors {
+		vector := vector
+		t.Run(vector.name, func(t *testing.T) {
+			input, err := hex.DecodeString(vector.bincode)
+			if err != nil {
+				t.Fatal(err)
+			}
+			obj, err := BincodeDeserializeTransactionStatusMeta(input)
+			if err != nil {
+				t.Fatalf("decode: %v", err)
+			}
+			if !reflect.DeepEqual(obj, vector.expected) {
+				t.Fatalf("decoded %#v, expected %#v", obj, vector.expected)
+			}
+			output, err := obj.BincodeSerialize()
+			if err != nil {
+				t.Fatalf("encode: %v", err)
+			}
+			if !bytes.Equal(output, input) {
+				t.Fatalf("encoded %x, expected %x", output, input)
+			}
+			rendered, err := obj.MarshalJSON()
+			if err != nil {
+				t.Fatalf("marshal: %v", err)
+			}
+			if !jsonEqual(t, rendered, []byte(vector.rpc)) {
+				t.Fatalf("marshalled %s, expected %s", rendered, vector.rpc)
+			}
+			var unmarshalled TransactionStatusMeta
+			if err := unmarshalled.UnmarshalJSON(rendered); err != nil {
+				t.Fatalf("unmarshal: %v", err)
+			}
+			if !reflect.DeepEqual(unmarshalled, obj) {
+				t.Fatalf("unmarshalled %#v, expected %#v", unmarshalled, obj)
+			}
+			for _, depth := range []uint64{vector.depth, vector.depth - 1} {
+				limits := DefaultDecodeLimits
+				limits.MaxContainerDepth = depth
+				deserializer, err := NewLimitedDeserializer(input, limits)
+				if err != nil {
+					t.Fatal(err)
+				}
+				_, err = DeserializeTransactionStatusMeta(deserializer)
+				var limitErr *LimitError
+				if depth == vector.depth && err != nil {
+					t.Fatalf("decode within a depth of %d: %v", depth, err)
+				}
+				if depth < vector.depth && !errors.As(err, &limitErr) {
+					t.Fatalf("decode within a depth of %d: expected a limit error, got %v", depth, err)
+				}
+			}
+		})
+	}
+}
+
+// jsonEqual compares JSON documents, keeping numbers as written.
+func jsonEqual(t *testing.T, a, b []byte) bool {
+	var values [2]interface{}
+	for i, data := range [][]byte{a, b} {
+		decoder := json.NewDecoder(bytes.NewReader(data))
+		decoder.UseNumber()
+		if err := decoder.Decode(&values[i]); err != nil {
+			t.Fatalf("invalid JSON %s: %v", data, err)
+		}
+	}
+	return reflect.DeepEqual(values[0], values[1])
+}
@@ -3,22 +3,22 @@ package parse_legacy_transaction_status_meta_ce598c5c98e7384c104fe7f5121e32c2c5a
 
 import (
 	"fmt"
-	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/serde"
-	"github.com/novifinancial/serde-reflection/serde-generate/runtime/golang/bincode"
+	"github.com/gagliardetto/solana-tx-meta-parser-generators/go/serde"
+	"github.com/gagliardetto/solana-tx-meta-parser-generators/go/bincode"
 )
 
 
 type CompiledInstruction struct {
 	ProgramIdIndex uint8
-	Accounts struct {Field0 struct {Field0 uint8}; Field1 uint8; Field2 uint8; Field3 uint8}
-	Data struct {Field0 struct {Field0 uint8}; Field1 uint8; Field2 uint8; Field3 uint8}
+	Accounts []uint8
+	Data []uint8
 }
 
 func (obj *CompiledInstruction) Serialize(serializer serde.Serializer) error {
 	if err := serializer.IncreaseContainerDepth(); err != nil { return err }
 	if err := serializer.SerializeU8(obj.ProgramIdIndex); err != nil { return err }
-	if err := serialize_tuple4_tuple1_u8_u8_u8_u8(obj.Accounts, serializer); err != nil { return err }
-	if err := serialize_tuple4_tuple1_u8_u8_u8_u8(obj.Data, serializer); err != nil { return err }
+	if err := serialize_short_vec_u8(obj.Accounts, serializer); err != nil { return err }
+	if err := serialize_short_vec_u8(obj.Data, serializer); err != nil { return err }
 	serializer.DecreaseContainerDepth()
 	return nil
 }
@@ -36,8 +36,8 @@ func DeserializeCompiledInstruction(deserializer serde.Deserializer) (CompiledIn
 	var obj CompiledInstruction
 	if err := deserializer.IncreaseContainerDepth(); err != nil { return obj, err }
 	if val, err := deserializer.DeserializeU8(); err == nil { obj.ProgramIdIndex = val } else { return obj, err }
-	if val, err := deserialize_tuple4_tuple1_u8_u8_u8_u8(deserializer); err == nil { obj.Accounts = val } else { return obj, err }
-	if val, err := deserialize_tuple4_tuple1_u8_u8_u8_u8(deserializer); err == nil { obj.Data = val } else { return obj, err }
+	if val, err := deserialize_short_vec_u8(deserializer); err == nil { obj.Accounts = val } else { return obj, err }
+	if val, err := deserialize_short_vec_u8(deserializer); err == nil { obj.Data = val } else { return obj, err }
 	deserializer.DecreaseContainerDepth()
 	return obj, nil
 }
@@ -47,7 +47,11 @@ func BincodeDeserializeCompiledInstruction(input []byte) (CompiledInstruction, e
 		var obj CompiledInstruction
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj CompiledInstruction
+		return obj, err
+	}
 	obj, err := DeserializeCompiledInstruction(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -91,7 +95,11 @@ func BincodeDeserializeInnerInstructions(input []byte) (InnerInstructions, error
 		var obj InnerInstructions
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj InnerInstructions
+		return obj, err
+	}
 	obj, err := DeserializeInnerInstructions(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -386,7 +394,11 @@ func BincodeDeserializeInstructionError(input []byte) (InstructionError, error)
 		var obj InstructionError
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj InstructionError
+		return obj, err
+	}
 	obj, err := DeserializeInstructionError(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -1494,7 +1506,11 @@ func BincodeDeserializeResult(input []byte) (Result, error) {
 		var obj Result
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj Result
+		return obj, err
+	}
 	obj, err := DeserializeResult(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -1695,7 +1711,11 @@ func BincodeDeserializeTransactionError(input []byte) (TransactionError, error)
 		var obj TransactionError
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj TransactionError
+		return obj, err
+	}
 	obj, err := DeserializeTransactionError(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -2202,7 +2222,11 @@ func BincodeDeserializeTransactionStatusMeta(input []byte) (TransactionStatusMet
 		var obj TransactionStatusMeta
 		return obj, fmt.Errorf("Cannot deserialize null array")
 	}
-	deserializer := bincode.NewDeserializer(input);
+	deserializer, err := NewLimitedDeserializer(input, DefaultDecodeLimits)
+	if err != nil {
+		var obj TransactionStatusMeta
+		return obj, err
+	}
 	obj, err := DeserializeTransactionStatusMeta(deserializer)
 	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {
 		return obj, fmt.Errorf("Some input bytes were not read")
@@ -2231,34 +2255,6 @@ func deserialize_option_vector_InnerInstructions(deserializer serde.Deserializer
 	}
 }
 
-func serialize_tuple1_u8(value struct {Field0 uint8}, serializer serde.Serializer) error {
-	if err := serializer.SerializeU8(value.Field0); err != nil { return err }
-	return nil
-}
-
-func deserialize_tuple1_u8(deserializer serde.Deserializer) (struct {Field0 uint8}, error) {
-	var obj struct {Field0 uint8}
-	if val, err := deserializer.DeserializeU8(); err == nil { obj.Field0 = val } else { return obj, err }
-	return obj, nil
-}
-
-func serialize_tuple4_tuple1_u8_u8_u8_u8(value struct {Field0 struct {Field0 uint8}; Field1 uint8; Field2 uint8; Field3 uint8}, serializer serde.Serializer) error {
-	if err := serialize_tuple1_u8(value.Field0, serializer); err != nil { return err }
-	if err := serializer.SerializeU8(value.Field1); err != nil { return err }
-	if err := serializer.SerializeU8(value.Field2); err != nil { return err }
-	if err := serializer.SerializeU8(value.Field3); err != nil { return err }
-	return nil
-}
-
-func deserialize_tuple4_tuple1_u8_u8_u8_u8(deserializer serde.Deserializer) (struct {Field0 struct {Field0 uint8}; Field1 uint8; Field2 uint8; Field3 uint8}, error) {
-	var obj struct {Field0 struct {Field0 uint8}; Field1 uint8; Field2 uint8; Field3 uint8}
-	if val, err := deserialize_tuple1_u8(deserializer); err == nil { obj.Field0 = val } else { return obj, err }
-	if val, err := deserializer.DeserializeU8(); err == nil { obj.Field1 = val } else { return obj, err }
-	if val, err := deserializer.DeserializeU8(); err == nil { obj.Field2 = val } else { return obj, err }
-	if val, err := deserializer.DeserializeU8(); err == nil { obj.Field3 = val } else { return obj, err }
-	return obj, nil
-}
-
 func serialize_vector_CompiledInstruction(value []CompiledInstruction, serializer serde.Serializer) error {
 	if err := serializer.SerializeLen(uint64(len(value))); err != nil { return err }
 	for _, item := range(value) {
@@ -2313,3 +2309,80 @@ func deserialize_vector_u64(deserializer serde.Deserializer) ([]uint64, error) {
 	return obj, nil
 }
 
+func serialize_vector_u8(value []uint8, serializer serde.Serializer) error {
+	if err := serializer.SerializeLen(uint64(len(value))); err != nil { return err }
+	for _, item := range(value) {
+		if err := serializer.SerializeU8(item); err != nil { return err }
+	}
+	return nil
+}
+
+func deserialize_vector_u8(deserializer serde.Deserializer) ([]uint8, error) {
+	length, err := deserializer.DeserializeLen()
+	if err != nil { return nil, err }
+	obj := make([]uint8, length)
+	for i := range(obj) {
+		if val, err := deserializer.DeserializeU8(); err == nil { obj[i] = val } else { return nil, err }
+	}
+	return obj, nil
+}
+
+
+// serialize_short_vec_u8 writes the length as a ShortU16: 7 bits per byte,
+// least significant first, with the high bit set on all but the last byte.
+func serialize_short_vec_u8(value []uint8, serializer serde.Serializer) error {
+	if len(value) > 0xffff {
+		return fmt.Errorf("short_vec length %d does not fit in a u16", len(value))
+	}
+	rem := len(value)
+	for {
+		elem := uint8(rem & 0x7f)
+		rem >>= 7
+		if rem == 0 {
+			if err := serializer.SerializeU8(elem); err != nil { return err }
+			break
+		}
+		if err := serializer.SerializeU8(elem | 0x80); err != nil { return err }
+	}
+	for _, item := range value {
+		if err := serializer.SerializeU8(item); err != nil { return err }
+	}
+	return nil
+}
+
+// deserialize_short_vec_len rejects the same encodings as the Rust ShortU16Visitor.
+func deserialize_short_vec_len(deserializer serde.Deserializer) (int, error) {
+	length := 0
+	for nth := 0; nth < 3; nth++ {
+		elem, err := deserializer.DeserializeU8()
+		if err != nil { return 0, err }
+		if elem == 0 && nth != 0 {
+			return 0, fmt.Errorf("short_vec length: alias encoding")
+		}
+		if nth == 2 && elem&0x80 != 0 {
+			return 0, fmt.Errorf("short_vec length: continue signal on byte-three")
+		}
+		length |= int(elem&0x7f) << (7 * nth)
+		if length > 0xffff {
+			return 0, fmt.Errorf("short_vec length %d does not fit in a u16", length)
+		}
+		if elem&0x80 == 0 {
+			return length, nil
+		}
+	}
+	panic("unreachable")
+}
+
+func deserialize_short_vec_u8(deserializer serde.Deserializer) ([]uint8, error) {
+	length, err := deserialize_short_vec_len(deserializer)
+	if err != nil { return nil, err }
+	// The limits file of the package defines limitedDeserializer.
+	if limited, ok := deserializer.(*limitedDeserializer); ok {
+		if err := limited.checkLength(uint64(length)); err != nil { return nil, err }
+	}
+	obj := make([]uint8, length)
+	for i := range obj {
+		if val, err := deserializer.DeserializeU8(); err == nil { obj[i] = val } else { return nil, err }
+	}
+	return obj, nil
+}
@@ -0,0 +1,778 @@
+package parse_legacy_transaction_status_meta_ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"strings"
+)
+
+// jsonObject writes the fields in order, as serde_json does.
+func jsonObject(keys []string, values []json.RawMessage) json.RawMessage {
+	var buffer bytes.Buffer
+	buffer.WriteByte('{')
+	for i, key := range keys {
+		if i > 0 {
+			buffer.WriteByte(',')
+		}
+		name, _ := json.Marshal(key)
+		buffer.Write(name)
+		buffer.WriteByte(':')
+		buffer.Write(values[i])
+	}
+	buffer.WriteByte('}')
+	return buffer.Bytes()
+}
+
+// jsonPrepend adds a field in front of those of an object written by jsonObject.
+func jsonPrepend(key string, value json.RawMessage, object json.RawMessage) json.RawMessage {
+	first := jsonObject([]string{key}, []json.RawMessage{value})
+	if len(object) == 2 {
+		return first
+	}
+	return append(append(first[:len(first)-1], ','), object[1:]...)
+}
+
+func jsonFields(data []byte) (map[string]json.RawMessage, error) {
+	var fields map[string]json.RawMessage
+	if err := json.Unmarshal(data, &fields); err != nil { return nil, err }
+	if fields == nil { return nil, fmt.Errorf("expected an object") }
+	return fields, nil
+}
+
+func jsonItems(data []byte, length int) ([]json.RawMessage, error) {
+	var items []json.RawMessage
+	if err := json.Unmarshal(data, &items); err != nil { return nil, err }
+	if len(items) != length { return nil, fmt.Errorf("expected %d items, got %d", length, len(items)) }
+	return items, nil
+}
+
+// jsonVariant splits an enum value, either the name of a variant without a
+// payload or an object with the variant name as its only key.
+func jsonVariant(data []byte) (string, json.RawMessage, error) {
+	var name string
+	if err := json.Unmarshal(data, &name); err == nil {
+		return name, nil, nil
+	}
+	fields, err := jsonFields(data)
+	if err != nil { return "", nil, err }
+	if len(fields) != 1 { return "", nil, fmt.Errorf("expected a single variant, got %d", len(fields)) }
+	for name, content := range fields {
+		return name, content, nil
+	}
+	panic("unreachable")
+}
+
+// isJSONNull also holds for missing fields, which serde reads as None.
+func isJSONNull(data []byte) bool {
+	trimmed := bytes.TrimSpace(data)
+	return len(trimmed) == 0 || bytes.Equal(trimmed, []byte("null"))
+}
+
+const base58Alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+func json_encode_base58(value []uint8) (json.RawMessage, error) {
+	zeros := 0
+	for zeros < len(value) && value[zeros] == 0 {
+		zeros++
+	}
+	// Base 58 digits, least significant first.
+	var digits []byte
+	for _, b := range value[zeros:] {
+		carry := int(b)
+		for i := range digits {
+			carry += int(digits[i]) << 8
+			digits[i] = byte(carry % 58)
+			carry /= 58
+		}
+		for carry > 0 {
+			digits = append(digits, byte(carry%58))
+			carry /= 58
+		}
+	}
+	var encoded strings.Builder
+	encoded.WriteString(strings.Repeat("1", zeros))
+	for i := len(digits) - 1; i >= 0; i-- {
+		encoded.WriteByte(base58Alphabet[digits[i]])
+	}
+	return json.Marshal(encoded.String())
+}
+
+func json_decode_base58(data json.RawMessage) ([]uint8, error) {
+	var encoded string
+	if err := json.Unmarshal(data, &encoded); err != nil { return nil, err }
+	zeros := 0
+	for zeros < len(encoded) && encoded[zeros] == '1' {
+		zeros++
+	}
+	// Bytes, least significant first.
+	var decoded []byte
+	for _, c := range encoded[zeros:] {
+		carry := strings.IndexRune(base58Alphabet, c)
+		if carry < 0 { return nil, fmt.Errorf("invalid base58 character %q", c) }
+		for i := range decoded {
+			carry += int(decoded[i]) * 58
+			decoded[i] = byte(carry & 0xff)
+			carry >>= 8
+		}
+		for carry > 0 {
+			decoded = append(decoded, byte(carry&0xff))
+			carry >>= 8
+		}
+	}
+	obj := make([]uint8, zeros+len(decoded))
+	for i, b := range decoded {
+		obj[len(obj)-1-i] = b
+	}
+	return obj, nil
+}
+
+func (obj CompiledInstruction) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	values := make([]json.RawMessage, 3)
+	if values[0], err = json_encode_u8(obj.ProgramIdIndex); err != nil { return nil, err }
+	if values[1], err = json_encode_vector_u8(obj.Accounts); err != nil { return nil, err }
+	if values[2], err = json_encode_base58(obj.Data); err != nil { return nil, err }
+	content = jsonObject([]string{"programIdIndex", "accounts", "data"}, values)
+	return content, nil
+}
+
+func (target *CompiledInstruction) UnmarshalJSON(content []byte) error {
+	var obj CompiledInstruction
+	fields, err := jsonFields(content)
+	if err != nil { return fmt.Errorf("CompiledInstruction: %w", err) }
+	if obj.ProgramIdIndex, err = json_decode_u8(fields["programIdIndex"]); err != nil { return fmt.Errorf("CompiledInstruction.programIdIndex: %w", err) }
+	if obj.Accounts, err = json_decode_vector_u8(fields["accounts"]); err != nil { return fmt.Errorf("CompiledInstruction.accounts: %w", err) }
+	if obj.Data, err = json_decode_base58(fields["data"]); err != nil { return fmt.Errorf("CompiledInstruction.data: %w", err) }
+	*target = obj
+	return nil
+}
+
+func (obj InnerInstructions) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	values := make([]json.RawMessage, 2)
+	if values[0], err = json_encode_u8(obj.Index); err != nil { return nil, err }
+	if values[1], err = json_encode_vector_CompiledInstruction(obj.Instructions); err != nil { return nil, err }
+	content = jsonObject([]string{"index", "instructions"}, values)
+	return content, nil
+}
+
+func (target *InnerInstructions) UnmarshalJSON(content []byte) error {
+	var obj InnerInstructions
+	fields, err := jsonFields(content)
+	if err != nil { return fmt.Errorf("InnerInstructions: %w", err) }
+	if obj.Index, err = json_decode_u8(fields["index"]); err != nil { return fmt.Errorf("InnerInstructions.index: %w", err) }
+	if obj.Instructions, err = json_decode_vector_CompiledInstruction(fields["instructions"]); err != nil { return fmt.Errorf("InnerInstructions.instructions: %w", err) }
+	*target = obj
+	return nil
+}
+
+func (obj InstructionError__GenericError) MarshalJSON() ([]byte, error) {
+	return json.Marshal("GenericError")
+}
+
+func (obj InstructionError__InvalidArgument) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidArgument")
+}
+
+func (obj InstructionError__InvalidInstructionData) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidInstructionData")
+}
+
+func (obj InstructionError__InvalidAccountData) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidAccountData")
+}
+
+func (obj InstructionError__AccountDataTooSmall) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountDataTooSmall")
+}
+
+func (obj InstructionError__InsufficientFunds) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InsufficientFunds")
+}
+
+func (obj InstructionError__IncorrectProgramId) MarshalJSON() ([]byte, error) {
+	return json.Marshal("IncorrectProgramId")
+}
+
+func (obj InstructionError__MissingRequiredSignature) MarshalJSON() ([]byte, error) {
+	return json.Marshal("MissingRequiredSignature")
+}
+
+func (obj InstructionError__AccountAlreadyInitialized) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountAlreadyInitialized")
+}
+
+func (obj InstructionError__UninitializedAccount) MarshalJSON() ([]byte, error) {
+	return json.Marshal("UninitializedAccount")
+}
+
+func (obj InstructionError__UnbalancedInstruction) MarshalJSON() ([]byte, error) {
+	return json.Marshal("UnbalancedInstruction")
+}
+
+func (obj InstructionError__ModifiedProgramId) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ModifiedProgramId")
+}
+
+func (obj InstructionError__ExternalAccountLamportSpend) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ExternalAccountLamportSpend")
+}
+
+func (obj InstructionError__ExternalAccountDataModified) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ExternalAccountDataModified")
+}
+
+func (obj InstructionError__ReadonlyLamportChange) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ReadonlyLamportChange")
+}
+
+func (obj InstructionError__ReadonlyDataModified) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ReadonlyDataModified")
+}
+
+func (obj InstructionError__DuplicateAccountIndex) MarshalJSON() ([]byte, error) {
+	return json.Marshal("DuplicateAccountIndex")
+}
+
+func (obj InstructionError__ExecutableModified) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ExecutableModified")
+}
+
+func (obj InstructionError__RentEpochModified) MarshalJSON() ([]byte, error) {
+	return json.Marshal("RentEpochModified")
+}
+
+func (obj InstructionError__NotEnoughAccountKeys) MarshalJSON() ([]byte, error) {
+	return json.Marshal("NotEnoughAccountKeys")
+}
+
+func (obj InstructionError__AccountDataSizeChanged) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountDataSizeChanged")
+}
+
+func (obj InstructionError__AccountNotExecutable) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountNotExecutable")
+}
+
+func (obj InstructionError__AccountBorrowFailed) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountBorrowFailed")
+}
+
+func (obj InstructionError__AccountBorrowOutstanding) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountBorrowOutstanding")
+}
+
+func (obj InstructionError__DuplicateAccountOutOfSync) MarshalJSON() ([]byte, error) {
+	return json.Marshal("DuplicateAccountOutOfSync")
+}
+
+func (obj InstructionError__InvalidError) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidError")
+}
+
+func (obj InstructionError__ExecutableDataModified) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ExecutableDataModified")
+}
+
+func (obj InstructionError__ExecutableLamportChange) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ExecutableLamportChange")
+}
+
+func (obj InstructionError__ExecutableAccountNotRentExempt) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ExecutableAccountNotRentExempt")
+}
+
+func (obj InstructionError__UnsupportedProgramId) MarshalJSON() ([]byte, error) {
+	return json.Marshal("UnsupportedProgramId")
+}
+
+func (obj InstructionError__CallDepth) MarshalJSON() ([]byte, error) {
+	return json.Marshal("CallDepth")
+}
+
+func (obj InstructionError__MissingAccount) MarshalJSON() ([]byte, error) {
+	return json.Marshal("MissingAccount")
+}
+
+func (obj InstructionError__ReentrancyNotAllowed) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ReentrancyNotAllowed")
+}
+
+func (obj InstructionError__MaxSeedLengthExceeded) MarshalJSON() ([]byte, error) {
+	return json.Marshal("MaxSeedLengthExceeded")
+}
+
+func (obj InstructionError__InvalidSeeds) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidSeeds")
+}
+
+func (obj InstructionError__InvalidRealloc) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidRealloc")
+}
+
+func (obj InstructionError__ComputationalBudgetExceeded) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ComputationalBudgetExceeded")
+}
+
+func (obj InstructionError__Custom) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	if content, err = json_encode_u32((uint32)(obj)); err != nil { return nil, err }
+	return jsonObject([]string{"Custom"}, []json.RawMessage{content}), nil
+}
+
+// UnmarshalJSONInstructionError reads a InstructionError as written by its MarshalJSON methods.
+func UnmarshalJSONInstructionError(data []byte) (InstructionError, error) {
+	name, content, err := jsonVariant(data)
+	if err != nil { return nil, fmt.Errorf("InstructionError: %w", err) }
+	switch name {
+	case "GenericError":
+		return &InstructionError__GenericError{}, nil
+	case "InvalidArgument":
+		return &InstructionError__InvalidArgument{}, nil
+	case "InvalidInstructionData":
+		return &InstructionError__InvalidInstructionData{}, nil
+	case "InvalidAccountData":
+		return &InstructionError__InvalidAccountData{}, nil
+	case "AccountDataTooSmall":
+		return &InstructionError__AccountDataTooSmall{}, nil
+	case "InsufficientFunds":
+		return &InstructionError__InsufficientFunds{}, nil
+	case "IncorrectProgramId":
+		return &InstructionError__IncorrectProgramId{}, nil
+	case "MissingRequiredSignature":
+		return &InstructionError__MissingRequiredSignature{}, nil
+	case "AccountAlreadyInitialized":
+		return &InstructionError__AccountAlreadyInitialized{}, nil
+	case "UninitializedAccount":
+		return &InstructionError__UninitializedAccount{}, nil
+	case "UnbalancedInstruction":
+		return &InstructionError__UnbalancedInstruction{}, nil
+	case "ModifiedProgramId":
+		return &InstructionError__ModifiedProgramId{}, nil
+	case "ExternalAccountLamportSpend":
+		return &InstructionError__ExternalAccountLamportSpend{}, nil
+	case "ExternalAccountDataModified":
+		return &InstructionError__ExternalAccountDataModified{}, nil
+	case "ReadonlyLamportChange":
+		return &InstructionError__ReadonlyLamportChange{}, nil
+	case "ReadonlyDataModified":
+		return &InstructionError__ReadonlyDataModified{}, nil
+	case "DuplicateAccountIndex":
+		return &InstructionError__DuplicateAccountIndex{}, nil
+	case "ExecutableModified":
+		return &InstructionError__ExecutableModified{}, nil
+	case "RentEpochModified":
+		return &InstructionError__RentEpochModified{}, nil
+	case "NotEnoughAccountKeys":
+		return &InstructionError__NotEnoughAccountKeys{}, nil
+	case "AccountDataSizeChanged":
+		return &InstructionError__AccountDataSizeChanged{}, nil
+	case "AccountNotExecutable":
+		return &InstructionError__AccountNotExecutable{}, nil
+	case "AccountBorrowFailed":
+		return &InstructionError__AccountBorrowFailed{}, nil
+	case "AccountBorrowOutstanding":
+		return &InstructionError__AccountBorrowOutstanding{}, nil
+	case "DuplicateAccountOutOfSync":
+		return &InstructionError__DuplicateAccountOutOfSync{}, nil
+	case "InvalidError":
+		return &InstructionError__InvalidError{}, nil
+	case "ExecutableDataModified":
+		return &InstructionError__ExecutableDataModified{}, nil
+	case "ExecutableLamportChange":
+		return &InstructionError__ExecutableLamportChange{}, nil
+	case "ExecutableAccountNotRentExempt":
+		return &InstructionError__ExecutableAccountNotRentExempt{}, nil
+	case "UnsupportedProgramId":
+		return &InstructionError__UnsupportedProgramId{}, nil
+	case "CallDepth":
+		return &InstructionError__CallDepth{}, nil
+	case "MissingAccount":
+		return &InstructionError__MissingAccount{}, nil
+	case "ReentrancyNotAllowed":
+		return &InstructionError__ReentrancyNotAllowed{}, nil
+	case "MaxSeedLengthExceeded":
+		return &InstructionError__MaxSeedLengthExceeded{}, nil
+	case "InvalidSeeds":
+		return &InstructionError__InvalidSeeds{}, nil
+	case "InvalidRealloc":
+		return &InstructionError__InvalidRealloc{}, nil
+	case "ComputationalBudgetExceeded":
+		return &InstructionError__ComputationalBudgetExceeded{}, nil
+	case "Custom":
+		var obj InstructionError__Custom
+		val, err := json_decode_u32(content)
+		if err != nil { return nil, fmt.Errorf("InstructionError::Custom: %w", err) }
+		obj = (InstructionError__Custom)(val)
+		return &obj, nil
+	default:
+		return nil, fmt.Errorf("unknown variant %q of InstructionError", name)
+	}
+}
+
+func (obj Result__Ok) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	if content, err = json_encode_unit((struct {})(obj)); err != nil { return nil, err }
+	return jsonObject([]string{"Ok"}, []json.RawMessage{content}), nil
+}
+
+func (obj Result__Err) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	if content, err = json_encode_TransactionError(obj.Value); err != nil { return nil, err }
+	return jsonObject([]string{"Err"}, []json.RawMessage{content}), nil
+}
+
+// UnmarshalJSONResult reads a Result as written by its MarshalJSON methods.
+func UnmarshalJSONResult(data []byte) (Result, error) {
+	name, content, err := jsonVariant(data)
+	if err != nil { return nil, fmt.Errorf("Result: %w", err) }
+	switch name {
+	case "Ok":
+		var obj Result__Ok
+		val, err := json_decode_unit(content)
+		if err != nil { return nil, fmt.Errorf("Result::Ok: %w", err) }
+		obj = (Result__Ok)(val)
+		return &obj, nil
+	case "Err":
+		var obj Result__Err
+		val, err := json_decode_TransactionError(content)
+		if err != nil { return nil, fmt.Errorf("Result::Err: %w", err) }
+		obj.Value = val
+		return &obj, nil
+	default:
+		return nil, fmt.Errorf("unknown variant %q of Result", name)
+	}
+}
+
+func (obj TransactionError__AccountInUse) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountInUse")
+}
+
+func (obj TransactionError__AccountLoadedTwice) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountLoadedTwice")
+}
+
+func (obj TransactionError__AccountNotFound) MarshalJSON() ([]byte, error) {
+	return json.Marshal("AccountNotFound")
+}
+
+func (obj TransactionError__ProgramAccountNotFound) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ProgramAccountNotFound")
+}
+
+func (obj TransactionError__InsufficientFundsForFee) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InsufficientFundsForFee")
+}
+
+func (obj TransactionError__InvalidAccountForFee) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidAccountForFee")
+}
+
+func (obj TransactionError__DuplicateSignature) MarshalJSON() ([]byte, error) {
+	return json.Marshal("DuplicateSignature")
+}
+
+func (obj TransactionError__BlockhashNotFound) MarshalJSON() ([]byte, error) {
+	return json.Marshal("BlockhashNotFound")
+}
+
+func (obj TransactionError__CallChainTooDeep) MarshalJSON() ([]byte, error) {
+	return json.Marshal("CallChainTooDeep")
+}
+
+func (obj TransactionError__MissingSignatureForFee) MarshalJSON() ([]byte, error) {
+	return json.Marshal("MissingSignatureForFee")
+}
+
+func (obj TransactionError__InvalidAccountIndex) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidAccountIndex")
+}
+
+func (obj TransactionError__SignatureFailure) MarshalJSON() ([]byte, error) {
+	return json.Marshal("SignatureFailure")
+}
+
+func (obj TransactionError__InvalidProgramForExecution) MarshalJSON() ([]byte, error) {
+	return json.Marshal("InvalidProgramForExecution")
+}
+
+func (obj TransactionError__SanitizeFailure) MarshalJSON() ([]byte, error) {
+	return json.Marshal("SanitizeFailure")
+}
+
+func (obj TransactionError__ClusterMaintenance) MarshalJSON() ([]byte, error) {
+	return json.Marshal("ClusterMaintenance")
+}
+
+func (obj TransactionError__InstructionError) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	items := make([]json.RawMessage, 2)
+	if items[0], err = json_encode_u8(obj.Field0); err != nil { return nil, err }
+	if items[1], err = json_encode_InstructionError(obj.Field1); err != nil { return nil, err }
+	if content, err = json.Marshal(items); err != nil { return nil, err }
+	return jsonObject([]string{"InstructionError"}, []json.RawMessage{content}), nil
+}
+
+// UnmarshalJSONTransactionError reads a TransactionError as written by its MarshalJSON methods.
+func UnmarshalJSONTransactionError(data []byte) (TransactionError, error) {
+	name, content, err := jsonVariant(data)
+	if err != nil { return nil, fmt.Errorf("TransactionError: %w", err) }
+	switch name {
+	case "AccountInUse":
+		return &TransactionError__AccountInUse{}, nil
+	case "AccountLoadedTwice":
+		return &TransactionError__AccountLoadedTwice{}, nil
+	case "AccountNotFound":
+		return &TransactionError__AccountNotFound{}, nil
+	case "ProgramAccountNotFound":
+		return &TransactionError__ProgramAccountNotFound{}, nil
+	case "InsufficientFundsForFee":
+		return &TransactionError__InsufficientFundsForFee{}, nil
+	case "InvalidAccountForFee":
+		return &TransactionError__InvalidAccountForFee{}, nil
+	case "DuplicateSignature":
+		return &TransactionError__DuplicateSignature{}, nil
+	case "BlockhashNotFound":
+		return &TransactionError__BlockhashNotFound{}, nil
+	case "CallChainTooDeep":
+		return &TransactionError__CallChainTooDeep{}, nil
+	case "MissingSignatureForFee":
+		return &TransactionError__MissingSignatureForFee{}, nil
+	case "InvalidAccountIndex":
+		return &TransactionError__InvalidAccountIndex{}, nil
+	case "SignatureFailure":
+		return &TransactionError__SignatureFailure{}, nil
+	case "InvalidProgramForExecution":
+		return &TransactionError__InvalidProgramForExecution{}, nil
+	case "SanitizeFailure":
+		return &TransactionError__SanitizeFailure{}, nil
+	case "ClusterMaintenance":
+		return &TransactionError__ClusterMaintenance{}, nil
+	case "InstructionError":
+		var obj TransactionError__InstructionError
+		items, err := jsonItems(content, 2)
+		if err != nil { return nil, fmt.Errorf("TransactionError::InstructionError: %w", err) }
+		if obj.Field0, err = json_decode_u8(items[0]); err != nil { return nil, fmt.Errorf("TransactionError::InstructionError.0: %w", err) }
+		if obj.Field1, err = json_decode_InstructionError(items[1]); err != nil { return nil, fmt.Errorf("TransactionError::InstructionError.1: %w", err) }
+		return &obj, nil
+	default:
+		return nil, fmt.Errorf("unknown variant %q of TransactionError", name)
+	}
+}
+
+func (obj TransactionStatusMeta) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	values := make([]json.RawMessage, 5)
+	if values[0], err = json_encode_Result(obj.Status); err != nil { return nil, err }
+	if values[1], err = json_encode_u64(obj.Fee); err != nil { return nil, err }
+	if values[2], err = json_encode_vector_u64(obj.PreBalances); err != nil { return nil, err }
+	if values[3], err = json_encode_vector_u64(obj.PostBalances); err != nil { return nil, err }
+	if values[4], err = json_encode_option_vector_InnerInstructions(obj.InnerInstructions); err != nil { return nil, err }
+	content = jsonObject([]string{"status", "fee", "preBalances", "postBalances", "innerInstructions"}, values)
+	errValue := json.RawMessage("null")
+	if status, ok := obj.Status.(*Result__Err); ok {
+		if errValue, err = json_encode_TransactionError(status.Value); err != nil { return nil, err }
+	}
+	content = jsonPrepend("err", errValue, content)
+	return content, nil
+}
+
+func (target *TransactionStatusMeta) UnmarshalJSON(content []byte) error {
+	var obj TransactionStatusMeta
+	fields, err := jsonFields(content)
+	if err != nil { return fmt.Errorf("TransactionStatusMeta: %w", err) }
+	if obj.Status, err = json_decode_Result(fields["status"]); err != nil { return fmt.Errorf("TransactionStatusMeta.status: %w", err) }
+	if obj.Fee, err = json_decode_u64(fields["fee"]); err != nil { return fmt.Errorf("TransactionStatusMeta.fee: %w", err) }
+	if obj.PreBalances, err = json_decode_vector_u64(fields["preBalances"]); err != nil { return fmt.Errorf("TransactionStatusMeta.preBalances: %w", err) }
+	if obj.PostBalances, err = json_decode_vector_u64(fields["postBalances"]); err != nil { return fmt.Errorf("TransactionStatusMeta.postBalances: %w", err) }
+	if obj.InnerInstructions, err = json_decode_option_vector_InnerInstructions(fields["innerInstructions"]); err != nil { return fmt.Errorf("TransactionStatusMeta.innerInstructions: %w", err) }
+	*target = obj
+	return nil
+}
+
+func json_encode_CompiledInstruction(value CompiledInstruction) (json.RawMessage, error) {
+	return value.MarshalJSON()
+}
+
+func json_decode_CompiledInstruction(data json.RawMessage) (CompiledInstruction, error) {
+	var obj CompiledInstruction
+	err := obj.UnmarshalJSON(data)
+	return obj, err
+}
+
+func json_encode_InnerInstructions(value InnerInstructions) (json.RawMessage, error) {
+	return value.MarshalJSON()
+}
+
+func json_decode_InnerInstructions(data json.RawMessage) (InnerInstructions, error) {
+	var obj InnerInstructions
+	err := obj.UnmarshalJSON(data)
+	return obj, err
+}
+
+func json_encode_InstructionError(value InstructionError) (json.RawMessage, error) {
+	return json.Marshal(value)
+}
+
+func json_decode_InstructionError(data json.RawMessage) (InstructionError, error) {
+	return UnmarshalJSONInstructionError(data)
+}
+
+func json_encode_Result(value Result) (json.RawMessage, error) {
+	return json.Marshal(value)
+}
+
+func json_decode_Result(data json.RawMessage) (Result, error) {
+	return UnmarshalJSONResult(data)
+}
+
+func json_encode_TransactionError(value TransactionError) (json.RawMessage, error) {
+	return json.Marshal(value)
+}
+
+func json_decode_TransactionError(data json.RawMessage) (TransactionError, error) {
+	return UnmarshalJSONTransactionError(data)
+}
+
+func json_encode_option_vector_InnerInstructions(value *[]InnerInstructions) (json.RawMessage, error) {
+	if value == nil { return json.RawMessage("null"), nil }
+	return json_encode_vector_InnerInstructions(*value)
+}
+
+func json_decode_option_vector_InnerInstructions(data json.RawMessage) (*[]InnerInstructions, error) {
+	if isJSONNull(data) { return nil, nil }
+	obj, err := json_decode_vector_InnerInstructions(data)
+	if err != nil { return nil, err }
+	return &obj, nil
+}
+
+func json_encode_u32(value uint32) (json.RawMessage, error) {
+	return json.Marshal(value)
+}
+
+func json_decode_u32(data json.RawMessage) (uint32, error) {
+	var obj uint32
+	err := json.Unmarshal(data, &obj)
+	return obj, err
+}
+
+func json_encode_u64(value uint64) (json.RawMessage, error) {
+	return json.Marshal(value)
+}
+
+func json_decode_u64(data json.RawMessage) (uint64, error) {
+	var obj uint64
+	err := json.Unmarshal(data, &obj)
+	return obj, err
+}
+
+func json_encode_u8(value uint8) (json.RawMessage, error) {
+	return json.Marshal(value)
+}
+
+func json_decode_u8(data json.RawMessage) (uint8, error) {
+	var obj uint8
+	err := json.Unmarshal(data, &obj)
+	return obj, err
+}
+
+func json_encode_unit(value struct {}) (json.RawMessage, error) {
+	return json.RawMessage("null"), nil
+}
+
+func json_decode_unit(data json.RawMessage) (struct {}, error) {
+	if !isJSONNull(data) { return struct {}{}, fmt.Errorf("expected null") }
+	return struct {}{}, nil
+}
+
+func json_encode_vector_CompiledInstruction(value []CompiledInstruction) (json.RawMessage, error) {
+	items := make([]json.RawMessage, len(value))
+	for i, item := range value {
+		var err error
+		if items[i], err = json_encode_CompiledInstruction(item); err != nil { return nil, err }
+	}
+	return json.Marshal(items)
+}
+
+func json_decode_vector_CompiledInstruction(data json.RawMessage) ([]CompiledInstruction, error) {
+	var obj []CompiledInstruction
+	var items []json.RawMessage
+	if err := json.Unmarshal(data, &items); err != nil { return obj, err }
+	obj = make([]CompiledInstruction, len(items))
+	for i, item := range items {
+		var err error
+		if obj[i], err = json_decode_CompiledInstruction(item); err != nil { return obj, err }
+	}
+	return obj, nil
+}
+
+func json_encode_vector_InnerInstructions(value []InnerInstructions) (json.RawMessage, error) {
+	items := make([]json.RawMessage, len(value))
+	for i, item := range value {
+		var err error
+		if items[i], err = json_encode_InnerInstructions(item); err != nil { return nil, err }
+	}
+	return json.Marshal(items)
+}
+
+func json_decode_vector_InnerInstructions(data json.RawMessage) ([]InnerInstructions, error) {
+	var obj []InnerInstructions
+	var items []json.RawMessage
+	if err := json.Unmarshal(data, &items); err != nil { return obj, err }
+	obj = make([]InnerInstructions, len(items))
+	for i, item := range items {
+		var err error
+		if obj[i], err = json_decode_InnerInstructions(item); err != nil { return obj, err }
+	}
+	return obj, nil
+}
+
+func json_encode_vector_u64(value []uint64) (json.RawMessage, error) {
+	items := make([]json.RawMessage, len(value))
+	for i, item := range value {
+		var err error
+		if items[i], err = json_encode_u64(item); err != nil { return nil, err }
+	}
+	return json.Marshal(items)
+}
+
+func json_decode_vector_u64(data json.RawMessage) ([]uint64, error) {
+	var obj []uint64
+	var items []json.RawMessage
+	if err := json.Unmarshal(data, &items); err != nil { return obj, err }
+	obj = make([]uint64, len(items))
+	for i, item := range items {
+		var err error
+		if obj[i], err = json_decode_u64(item); err != nil { return obj, err }
+	}
+	return obj, nil
+}
+
+func json_encode_vector_u8(value []uint8) (json.RawMessage, error) {
+	items := make([]json.RawMessage, len(value))
+	for i, item := range value {
+		var err error
+		if items[i], err = json_encode_u8(item); err != nil { return nil, err }
+	}
+	return json.Marshal(items)
+}
+
+func json_decode_vector_u8(data json.RawMessage) ([]uint8, error) {
+	var obj []uint8
+	var items []json.RawMessage
+	if err := json.Unmarshal(data, &items); err != nil { return obj, err }
+	obj = make([]uint8, len(items))
+	for i, item := range items {
+		var err error
+		if obj[i], err = json_decode_u8(item); err != nil { return obj, err }
+	}
+	return obj, nil
+}
@@ -0,0 +1,14 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+package serde
+
+type Uint128 struct {
+	High uint64
+	Low  uint64
+}
+
+type Int128 struct {
+	High int64
+	Low  uint64
+}
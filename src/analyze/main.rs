@@ -0,0 +1,47 @@
+use demo_serde_txmeta::batch::Input;
+use demo_serde_txmeta::census::Census;
+use demo_serde_txmeta::legacy::limits::Limits;
+use std::io::{self, Write};
+
+const USAGE: &str =
+    "usage: analyze [--input=raw|hex|base64|base58] [--compressed] [file]... (- or nothing for stdin)";
+
+// Decodes a corpus of metas and prints, as YAML, what it is made of.
+fn main() {
+    let mut input = Input::default();
+    let mut compressed = false;
+    let mut files = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.split_once('=') {
+            Some(("--input", format)) => match format.parse() {
+                Ok(format) => input = format,
+                Err(e) => {
+                    eprintln!("error: {}\n{}", e, USAGE);
+                    std::process::exit(2);
+                }
+            },
+            _ if arg == "--compressed" => compressed = true,
+            _ if arg.starts_with("--") => {
+                eprintln!("error: unknown argument {:?}\n{}", arg, USAGE);
+                std::process::exit(2);
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        files.push("-".to_string());
+    }
+
+    let limits = Limits::default();
+    let mut census = Census::default();
+    for file in &files {
+        census.add_file(input, file, compressed, &limits);
+    }
+    let report = serde_yaml::to_string(&census).unwrap();
+    if let Err(e) = io::stdout().write_all(report.as_bytes()) {
+        if e.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("error: writing output: {}", e);
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use crate::bigtable;
+use crate::legacy::{
+    self,
+    limits::{Exceeded, Limits},
+    validate::Violation,
+    TransactionStatusMeta,
+};
+use crate::rpc;
+use crate::schema::{self, Schema};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("unknown input format {0:?}, expected raw, hex, base64 or base58")]
+    UnknownInput(String),
+    #[error("raw input has no lines")]
+    RawLine,
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid hex: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("invalid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("invalid base58: {0}")]
+    Base58(#[from] bs58::decode::Error),
+    #[error("decode limit exceeded: {0}")]
+    Limit(#[from] Exceeded),
+    #[error("{0}")]
+    Legacy(#[from] legacy::Error),
+    #[error("{0}")]
+    BigTable(#[from] bigtable::Error),
+    #[error("schema error: {0}")]
+    Schema(#[from] schema::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("RPC rendering error: {0}")]
+    Rpc(#[from] rpc::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// How blobs are given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Input {
+    /// A whole file per blob.
+    #[default]
+    Raw,
+    /// A blob per line, in hex with or without a `0x` prefix.
+    Hex,
+    /// A blob per line, in standard base64.
+    Base64,
+    /// A blob per line, in base58.
+    Base58,
+}
+
+impl FromStr for Input {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "raw" => Ok(Self::Raw),
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            "base58" => Ok(Self::Base58),
+            _ => Err(Error::UnknownInput(s.to_string())),
+        }
+    }
+}
+
+impl Input {
+    /// The blob on a line of a text input, surrounding whitespace ignored.
+    pub fn line(&self, line: &str) -> Result<Vec<u8>> {
+        let line = line.trim();
+        let blob = match self {
+            Input::Raw => return Err(Error::RawLine),
+            Input::Hex => hex::decode(line.strip_prefix("0x").unwrap_or(line))?,
+            Input::Base64 => base64::decode(line)?,
+            Input::Base58 => bs58::decode(line).into_vec()?,
+        };
+        Ok(blob)
+    }
+}
+
+/// Reads the blobs of `file`, `-` being stdin, handing each to `emit` along
+/// with where it comes from, as `file` or `file:line`. Errors are handed over
+/// in place of the blob; one reading the file ends it. Raw files are not read
+/// past `limits.max_bytes`, nor lines past the longest encoding of it.
+pub fn read(
+    input: Input,
+    file: &str,
+    limits: &Limits,
+    emit: &mut dyn FnMut(String, Result<Vec<u8>>),
+) {
+    let reader: Box<dyn Read> = if file == "-" {
+        Box::new(io::stdin())
+    } else {
+        match File::open(file) {
+            Ok(reader) => Box::new(reader),
+            Err(e) => return emit(file.to_string(), Err(e.into())),
+        }
+    };
+    if input == Input::Raw {
+        return emit(file.to_string(), read_raw(reader, limits));
+    }
+    let mut reader = BufReader::new(reader);
+    let max_len = max_line_len(limits);
+    for number in 1.. {
+        let source = format!("{}:{}", file, number);
+        match read_line(&mut reader, max_len) {
+            Ok(None) => break,
+            Ok(Some(Ok(line))) if line.trim().is_empty() => continue,
+            Ok(Some(line)) => emit(source, line.and_then(|line| input.line(&line))),
+            Err(e) => {
+                emit(source, Err(e.into()));
+                break;
+            }
+        }
+    }
+}
+
+/// Room for `limits.max_bytes` in hex, the longest of the text encodings,
+/// with its `0x` prefix and some surrounding whitespace.
+fn max_line_len(limits: &Limits) -> usize {
+    limits.max_bytes.saturating_mul(2).saturating_add(16)
+}
+
+/// The next line of `reader` without its line ending, or `None` at the end.
+/// Lines over `max_len` bytes are skipped rather than held, and fail, as do
+/// lines that are not UTF-8.
+fn read_line(reader: &mut impl BufRead, max_len: usize) -> io::Result<Option<Result<String>>> {
+    let mut line = Vec::new();
+    if (&mut *reader)
+        .take(max_len as u64 + 1)
+        .read_until(b'\n', &mut line)?
+        == 0
+    {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    } else if line.len() > max_len {
+        let mut len = line.len();
+        loop {
+            let buffer = reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            match buffer.iter().position(|&byte| byte == b'\n') {
+                Some(position) => {
+                    len += position;
+                    reader.consume(position + 1);
+                    break;
+                }
+                None => {
+                    let read = buffer.len();
+                    len += read;
+                    reader.consume(read);
+                }
+            }
+        }
+        return Ok(Some(Err(Exceeded::Bytes(len, max_len).into())));
+    }
+    Ok(Some(String::from_utf8(line).map_err(|e| {
+        io::Error::new(io::ErrorKind::InvalidData, e).into()
+    })))
+}
+
+fn read_raw(reader: impl Read, limits: &Limits) -> Result<Vec<u8>> {
+    let mut blob = Vec::new();
+    reader
+        .take(limits.max_bytes as u64 + 1)
+        .read_to_end(&mut blob)?;
+    if blob.len() > limits.max_bytes {
+        return Err(Exceeded::Bytes(blob.len(), limits.max_bytes).into());
+    }
+    Ok(blob)
+}
+
+/// One line of NDJSON output: either the detected version and the meta, or
+/// the error met along the way.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    /// Where the blob comes from, as `file` or `file:line`.
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The meta in the RPC rendering.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The rules the meta breaks, when validating.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<Violation>,
+}
+
+/// Decodes blobs of any legacy layout into records.
+pub struct Decoder {
+    limits: Limits,
+    schemas: BTreeMap<&'static str, Schema>,
+    compressed: bool,
+    validate: bool,
+}
+
+impl Decoder {
+    pub fn new(limits: Limits) -> Result<Self> {
+        let mut schemas = BTreeMap::new();
+        for commit in [legacy::b7b4::COMMIT, legacy::ce598::COMMIT] {
+            schemas.insert(commit, Schema::builtin(commit)?);
+        }
+        Ok(Self {
+            limits,
+            schemas,
+            compressed: false,
+            validate: false,
+        })
+    }
+
+    /// Takes blobs out of the BigTable compression envelope before decoding them.
+    pub fn compressed(mut self) -> Self {
+        self.compressed = true;
+        self
+    }
+
+    /// Checks decoded metas against the validation rules, standalone.
+    pub fn validate(mut self) -> Self {
+        self.validate = true;
+        self
+    }
+
+    /// Decodes a blob, detecting its layout, and renders it the way the RPC does.
+    pub fn decode(&self, bytes: &[u8]) -> Result<(TransactionStatusMeta, Value)> {
+        let decompressed;
+        let bytes = if self.compressed {
+            decompressed = bigtable::decompress(bytes, &self.limits)?;
+            &decompressed
+        } else {
+            bytes
+        };
+        let meta = legacy::decode_with_limits(bytes, &self.limits)?;
+        let value = match &meta {
+            TransactionStatusMeta::B7b4(meta) => serde_json::to_value(meta)?,
+            TransactionStatusMeta::Ce598(meta) => serde_json::to_value(meta)?,
+        };
+        let schema = &self.schemas[meta.commit()];
+        let rendered = rpc::render(&schema.registry, &schema.root, &schema.short_vec, &value)?;
+        Ok((meta, rendered))
+    }
+
+    /// The record of the blob read from `source`, or of the error reading it.
+    pub fn record(&self, source: String, blob: Result<Vec<u8>>) -> Record {
+        match blob.and_then(|bytes| self.decode(&bytes)) {
+            Ok((meta, rendered)) => Record {
+                source,
+                version: Some(meta.commit().to_string()),
+                violations: if self.validate {
+                    meta.validate(None)
+                } else {
+                    Vec::new()
+                },
+                meta: Some(rendered),
+                error: None,
+            },
+            Err(e) => Record {
+                source,
+                version: None,
+                meta: None,
+                error: Some(e.to_string()),
+                violations: Vec::new(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_input_is_not_read_past_the_byte_limit() {
+        let limits = Limits {
+            max_bytes: 4,
+            ..Limits::default()
+        };
+        assert_eq!(
+            read_raw(&[1, 2, 3, 4][..], &limits).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+        // An endless reader stops one byte past the limit.
+        let err = read_raw(io::repeat(0), &limits).unwrap_err();
+        assert!(matches!(err, Error::Limit(Exceeded::Bytes(5, 4))));
+    }
+
+    #[test]
+    fn lines_in_every_text_input() {
+        assert_eq!(Input::Hex.line(" 0x0aff\n").unwrap(), vec![10, 255]);
+        assert_eq!(Input::Hex.line("0aff").unwrap(), vec![10, 255]);
+        assert_eq!(Input::Base64.line("Cv8=").unwrap(), vec![10, 255]);
+        assert_eq!(Input::Base58.line("qY").unwrap(), vec![10, 255]);
+        assert!(matches!(Input::Hex.line("0g"), Err(Error::Hex(_))));
+        assert!(matches!(Input::Raw.line("0aff"), Err(Error::RawLine)));
+    }
+
+    fn lines(text: &[u8], max_len: usize) -> Vec<std::result::Result<String, String>> {
+        let mut reader = text;
+        let mut lines = Vec::new();
+        while let Some(line) = read_line(&mut reader, max_len).unwrap() {
+            lines.push(line.map_err(|e| e.to_string()));
+        }
+        lines
+    }
+
+    #[test]
+    fn long_lines_are_skipped() {
+        let text = b"0aff\n0123456789\r\nabc\n0123456789abcdef\nlast";
+        assert_eq!(
+            lines(text, 11),
+            vec![
+                Ok("0aff".to_string()),
+                Ok("0123456789\r".to_string()),
+                Ok("abc".to_string()),
+                Err("decode limit exceeded: 16 bytes are over the limit of 11".to_string()),
+                Ok("last".to_string()),
+            ]
+        );
+        // A long last line without an ending.
+        assert_eq!(
+            lines(b"abc\n0123456789abcdef", 11),
+            vec![
+                Ok("abc".to_string()),
+                Err("decode limit exceeded: 16 bytes are over the limit of 11".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn lines_that_are_not_utf8_only_fail_themselves() {
+        let lines = lines(b"ab\xff\ncd\n", 16);
+        assert!(lines[0].is_err());
+        assert_eq!(lines[1], Ok("cd".to_string()));
+    }
+
+    #[test]
+    fn text_input_is_bounded_by_the_byte_limit() {
+        let limits = Limits {
+            max_bytes: 2,
+            ..Limits::default()
+        };
+        let file = std::env::temp_dir().join(format!("batch-lines-{}", std::process::id()));
+        let long = "ab".repeat(100);
+        std::fs::write(&file, format!("0x0aff\n{}\n", long)).unwrap();
+        let mut blobs = Vec::new();
+        read(
+            Input::Hex,
+            file.to_str().unwrap(),
+            &limits,
+            &mut |_, blob| blobs.push(blob),
+        );
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(blobs[0].as_ref().unwrap(), &vec![10, 255]);
+        assert!(matches!(
+            blobs[1],
+            Err(Error::Limit(Exceeded::Bytes(200, 20)))
+        ));
+    }
+}
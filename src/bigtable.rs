@@ -0,0 +1,364 @@
+pub mod stored;
+
+use crate::legacy::{
+    self,
+    limits::{self, Exceeded, Limits},
+};
+use crate::meta::{self, Decoded};
+use prost::Message;
+use solana_storage_proto::convert::generated;
+use solana_transaction_status::ConfirmedBlock;
+use std::io::{self, Read};
+use std::str::FromStr;
+use stored::StoredConfirmedBlock;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("a cell of {0} bytes is too short for its compression method")]
+    TooShort(usize),
+    #[error("unknown compression method {0}")]
+    UnknownMethod(u32),
+    #[error("decode limit exceeded: {0}")]
+    Limit(#[from] Exceeded),
+    #[error("unknown cell name {0:?}, expected bin or proto")]
+    UnknownCell(String),
+    #[error("{0}")]
+    Legacy(#[from] legacy::Error),
+    #[error("invalid protobuf block: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+    #[error("invalid protobuf block contents: {0}")]
+    ProtobufContents(#[from] bincode::Error),
+    #[error("a versioned transaction is stored without a meta")]
+    MissingMeta,
+    #[error("{0}")]
+    Meta(#[from] meta::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// From https://github.com/solana-labs/solana/blob/v1.14.18/storage-bigtable/src/compression.rs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMethod {
+    NoCompression,
+    Bzip2,
+    Gzip,
+    Zstd,
+}
+
+/// Takes a cell out of its compression envelope, the bincode tag of its
+/// `CompressionMethod` followed by the compressed data, without inflating it
+/// past `limits.max_bytes`.
+pub fn decompress(data: &[u8], limits: &Limits) -> Result<Vec<u8>> {
+    if data.len() < 4 {
+        return Err(Error::TooShort(data.len()));
+    }
+    let (tag, compressed) = data.split_at(4);
+    let method = match u32::from_le_bytes(tag.try_into().unwrap()) {
+        0 => CompressionMethod::NoCompression,
+        1 => CompressionMethod::Bzip2,
+        2 => CompressionMethod::Gzip,
+        3 => CompressionMethod::Zstd,
+        tag => return Err(Error::UnknownMethod(tag)),
+    };
+    let reader: Box<dyn Read> = match method {
+        CompressionMethod::NoCompression => Box::new(compressed),
+        CompressionMethod::Bzip2 => Box::new(bzip2::read::BzDecoder::new(compressed)),
+        CompressionMethod::Gzip => Box::new(flate2::read::GzDecoder::new(compressed)),
+        CompressionMethod::Zstd => Box::new(zstd::stream::read::Decoder::new(compressed)?),
+    };
+    let mut decompressed = Vec::new();
+    reader
+        .take(limits.max_bytes as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > limits.max_bytes {
+        return Err(Exceeded::Bytes(decompressed.len(), limits.max_bytes).into());
+    }
+    Ok(decompressed)
+}
+
+/// How a cell is encoded, which its name tells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    /// `bin`
+    Bincode,
+    /// `proto`
+    Protobuf,
+}
+
+impl FromStr for Cell {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "bin" => Ok(Self::Bincode),
+            "proto" => Ok(Self::Protobuf),
+            _ => Err(Error::UnknownCell(s.to_string())),
+        }
+    }
+}
+
+/// Decodes a cell of the `blocks` table.
+pub fn decode_block(cell: Cell, data: &[u8], limits: &Limits) -> Result<ConfirmedBlock> {
+    let bytes = decompress(data, limits)?;
+    match cell {
+        Cell::Protobuf => Ok(generated::ConfirmedBlock::decode(bytes.as_slice())?.try_into()?),
+        Cell::Bincode => limits::deserialize::<StoredConfirmedBlock>(&bytes, limits)?.try_into(),
+    }
+}
+
+/// Decodes a compressed meta, in any encoding.
+pub fn decode_meta(data: &[u8], limits: &Limits) -> Result<Decoded> {
+    let bytes = decompress(data, limits)?;
+    Ok(meta::decode(&bytes, limits)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::b7b4;
+    use crate::meta::{Encoding, Version};
+    use solana_sdk::message::Message as LegacyMessage;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::transaction::{Transaction, TransactionError, VersionedTransaction};
+    use solana_transaction_status::{
+        Reward, RewardType, TransactionStatusMeta, TransactionWithStatusMeta,
+        VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
+    };
+    use std::io::Write;
+    use stored::{
+        StoredConfirmedBlockReward, StoredConfirmedBlockTransaction,
+        StoredConfirmedBlockTransactionStatusMeta,
+    };
+
+    fn envelope(tag: u32, compressed: &[u8]) -> Vec<u8> {
+        [&tag.to_le_bytes()[..], compressed].concat()
+    }
+
+    fn data() -> Vec<u8> {
+        (0..4096u32).map(|n| (n % 7) as u8).collect()
+    }
+
+    #[test]
+    fn no_compression() {
+        let cell = envelope(0, &data());
+        assert_eq!(decompress(&cell, &Limits::default()).unwrap(), data());
+    }
+
+    #[test]
+    fn bzip2() {
+        let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::best());
+        encoder.write_all(&data()).unwrap();
+        let cell = envelope(1, &encoder.finish().unwrap());
+        assert_eq!(decompress(&cell, &Limits::default()).unwrap(), data());
+    }
+
+    #[test]
+    fn gzip() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&data()).unwrap();
+        let cell = envelope(2, &encoder.finish().unwrap());
+        assert_eq!(decompress(&cell, &Limits::default()).unwrap(), data());
+    }
+
+    #[test]
+    fn zstd() {
+        let cell = envelope(3, &zstd::stream::encode_all(data().as_slice(), 0).unwrap());
+        assert_eq!(decompress(&cell, &Limits::default()).unwrap(), data());
+    }
+
+    #[test]
+    fn unknown_method() {
+        let cell = envelope(4, &data());
+        assert!(matches!(
+            decompress(&cell, &Limits::default()),
+            Err(Error::UnknownMethod(4))
+        ));
+    }
+
+    #[test]
+    fn too_short_for_a_method() {
+        assert!(matches!(
+            decompress(&[0, 0, 0], &Limits::default()),
+            Err(Error::TooShort(3))
+        ));
+    }
+
+    #[test]
+    fn output_over_the_byte_limit() {
+        let limits = Limits {
+            max_bytes: 1024,
+            ..Limits::default()
+        };
+        let compressed = zstd::stream::encode_all(&[0u8; 1 << 20][..], 0).unwrap();
+        for cell in [envelope(0, &data()), envelope(3, &compressed)] {
+            assert!(matches!(
+                decompress(&cell, &limits),
+                Err(Error::Limit(Exceeded::Bytes(1025, 1024)))
+            ));
+        }
+        // Exactly at the limit is fine.
+        let cell = envelope(0, &data()[..1024]);
+        assert_eq!(decompress(&cell, &limits).unwrap().len(), 1024);
+    }
+
+    fn transaction() -> Transaction {
+        Transaction::new_unsigned(LegacyMessage::new(&[], Some(&Pubkey::new_unique())))
+    }
+
+    fn meta() -> TransactionStatusMeta {
+        TransactionStatusMeta {
+            status: Err(TransactionError::AccountInUse),
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![4000, 1000],
+            // What protobuf decodes absent lists to.
+            pre_token_balances: Some(vec![]),
+            post_token_balances: Some(vec![]),
+            rewards: Some(vec![]),
+            ..TransactionStatusMeta::default()
+        }
+    }
+
+    #[test]
+    fn protobuf_block() {
+        let block = VersionedConfirmedBlock {
+            previous_blockhash: "previous".to_string(),
+            blockhash: "hash".to_string(),
+            parent_slot: 41,
+            transactions: vec![VersionedTransactionWithStatusMeta {
+                transaction: transaction().into(),
+                meta: meta(),
+            }],
+            rewards: vec![Reward {
+                pubkey: Pubkey::new_unique().to_string(),
+                lamports: 10,
+                post_balance: 1000,
+                reward_type: Some(RewardType::Fee),
+                commission: None,
+            }],
+            block_time: Some(1_600_000_000),
+            block_height: Some(40),
+        };
+        let bytes = generated::ConfirmedBlock::from(block.clone()).encode_to_vec();
+        let cell = envelope(3, &zstd::stream::encode_all(bytes.as_slice(), 0).unwrap());
+        assert_eq!(
+            decode_block(Cell::Protobuf, &cell, &Limits::default()).unwrap(),
+            ConfirmedBlock::from(block)
+        );
+    }
+
+    #[test]
+    fn bincode_block() {
+        let (complete, missing) = (transaction(), transaction());
+        let reward = Pubkey::new_unique().to_string();
+        let stored = StoredConfirmedBlock {
+            previous_blockhash: "previous".to_string(),
+            blockhash: "hash".to_string(),
+            parent_slot: 41,
+            transactions: vec![
+                StoredConfirmedBlockTransaction {
+                    transaction: complete.clone().into(),
+                    meta: Some(StoredConfirmedBlockTransactionStatusMeta {
+                        err: Some(TransactionError::AccountInUse),
+                        fee: 5000,
+                        pre_balances: vec![10_000, 0],
+                        post_balances: vec![4000, 1000],
+                    }),
+                },
+                StoredConfirmedBlockTransaction {
+                    transaction: missing.clone().into(),
+                    meta: None,
+                },
+            ],
+            rewards: vec![StoredConfirmedBlockReward {
+                pubkey: reward.clone(),
+                lamports: 10,
+            }],
+            block_time: Some(1_600_000_000),
+            block_height: None,
+        };
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder
+            .write_all(&bincode::serialize(&stored).unwrap())
+            .unwrap();
+        let cell = envelope(2, &encoder.finish().unwrap());
+
+        // The stored meta has no lists beyond the balances.
+        let meta = TransactionStatusMeta {
+            pre_token_balances: None,
+            post_token_balances: None,
+            rewards: None,
+            ..meta()
+        };
+        let block = ConfirmedBlock {
+            previous_blockhash: "previous".to_string(),
+            blockhash: "hash".to_string(),
+            parent_slot: 41,
+            transactions: vec![
+                TransactionWithStatusMeta::Complete(VersionedTransactionWithStatusMeta {
+                    transaction: VersionedTransaction::from(complete),
+                    meta,
+                }),
+                TransactionWithStatusMeta::MissingMetadata(missing),
+            ],
+            rewards: vec![Reward {
+                pubkey: reward,
+                lamports: 10,
+                post_balance: 0,
+                reward_type: None,
+                commission: None,
+            }],
+            block_time: Some(1_600_000_000),
+            block_height: None,
+        };
+        assert_eq!(
+            decode_block(Cell::Bincode, &cell, &Limits::default()).unwrap(),
+            block
+        );
+        assert!(matches!(
+            decode_block(Cell::Protobuf, &cell, &Limits::default()),
+            Err(Error::Protobuf(_))
+        ));
+    }
+
+    #[test]
+    fn protobuf_meta() {
+        let bytes = generated::TransactionStatusMeta::from(meta()).encode_to_vec();
+        let mut encoder = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::best());
+        encoder.write_all(&bytes).unwrap();
+        let cell = envelope(1, &encoder.finish().unwrap());
+        let decoded = decode_meta(&cell, &Limits::default()).unwrap();
+        assert_eq!(
+            (decoded.encoding, decoded.version),
+            (Encoding::Protobuf, Version::Current)
+        );
+        assert_eq!(decoded.meta, meta());
+    }
+
+    #[test]
+    fn bincode_meta() {
+        let legacy = b7b4::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![4000, 1000],
+        };
+        let bytes = b7b4::to_bincode(&legacy).unwrap();
+        let cell = envelope(3, &zstd::stream::encode_all(bytes.as_slice(), 0).unwrap());
+        let decoded = decode_meta(&cell, &Limits::default()).unwrap();
+        assert_eq!(
+            (decoded.encoding, decoded.version),
+            (Encoding::Bincode, Version::Legacy(b7b4::COMMIT))
+        );
+        assert_eq!(decoded.meta.fee, 5000);
+        assert_eq!(decoded.meta.post_balances, legacy.post_balances);
+
+        assert!(matches!(
+            decode_meta(&envelope(0, &[]), &Limits::default()),
+            Err(Error::Meta(meta::Error::Empty))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use solana_sdk::clock::{Slot, UnixTimestamp};
+use solana_sdk::deserialize_utils::default_on_eof;
+use solana_sdk::transaction::{TransactionError, VersionedTransaction};
+use solana_transaction_status::{
+    ConfirmedBlock, Reward, TransactionStatusMeta, TransactionWithStatusMeta,
+    VersionedTransactionWithStatusMeta,
+};
+
+// From https://github.com/solana-labs/solana/blob/v1.14.18/storage-bigtable/src/lib.rs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredConfirmedBlock {
+    pub previous_blockhash: String,
+    pub blockhash: String,
+    pub parent_slot: Slot,
+    pub transactions: Vec<StoredConfirmedBlockTransaction>,
+    pub rewards: StoredConfirmedBlockRewards,
+    pub block_time: Option<UnixTimestamp>,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub block_height: Option<u64>,
+}
+
+// From https://github.com/solana-labs/solana/blob/v1.14.18/storage-bigtable/src/lib.rs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredConfirmedBlockTransaction {
+    pub transaction: VersionedTransaction,
+    pub meta: Option<StoredConfirmedBlockTransactionStatusMeta>,
+}
+
+// From https://github.com/solana-labs/solana/blob/v1.14.18/storage-bigtable/src/lib.rs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StoredConfirmedBlockTransactionStatusMeta {
+    pub err: Option<TransactionError>,
+    pub fee: u64,
+    pub pre_balances: Vec<u64>,
+    pub post_balances: Vec<u64>,
+}
+
+// From https://github.com/solana-labs/solana/blob/v1.14.18/storage-bigtable/src/lib.rs
+pub type StoredConfirmedBlockRewards = Vec<StoredConfirmedBlockReward>;
+
+// From https://github.com/solana-labs/solana/blob/v1.14.18/storage-bigtable/src/lib.rs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StoredConfirmedBlockReward {
+    pub pubkey: String,
+    pub lamports: i64,
+}
+
+impl TryFrom<StoredConfirmedBlock> for ConfirmedBlock {
+    type Error = super::Error;
+
+    fn try_from(block: StoredConfirmedBlock) -> Result<Self, Self::Error> {
+        Ok(Self {
+            previous_blockhash: block.previous_blockhash,
+            blockhash: block.blockhash,
+            parent_slot: block.parent_slot,
+            transactions: block
+                .transactions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            rewards: block.rewards.into_iter().map(Into::into).collect(),
+            block_time: block.block_time,
+            block_height: block.block_height,
+        })
+    }
+}
+
+impl TryFrom<StoredConfirmedBlockTransaction> for TransactionWithStatusMeta {
+    type Error = super::Error;
+
+    fn try_from(transaction: StoredConfirmedBlockTransaction) -> Result<Self, Self::Error> {
+        match transaction.meta {
+            // Only legacy transactions were ever stored without a meta.
+            None => transaction
+                .transaction
+                .into_legacy_transaction()
+                .map(Self::MissingMetadata)
+                .ok_or(super::Error::MissingMeta),
+            Some(meta) => Ok(Self::Complete(VersionedTransactionWithStatusMeta {
+                transaction: transaction.transaction,
+                meta: meta.into(),
+            })),
+        }
+    }
+}
+
+impl From<StoredConfirmedBlockTransactionStatusMeta> for TransactionStatusMeta {
+    fn from(meta: StoredConfirmedBlockTransactionStatusMeta) -> Self {
+        Self {
+            status: meta.err.map_or(Ok(()), Err),
+            fee: meta.fee,
+            pre_balances: meta.pre_balances,
+            post_balances: meta.post_balances,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<StoredConfirmedBlockReward> for Reward {
+    fn from(reward: StoredConfirmedBlockReward) -> Self {
+        Self {
+            pubkey: reward.pubkey,
+            lamports: reward.lamports,
+            post_balance: 0,
+            reward_type: None,
+            commission: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::message::{v0, VersionedMessage};
+
+    fn block(transactions: Vec<StoredConfirmedBlockTransaction>) -> StoredConfirmedBlock {
+        StoredConfirmedBlock {
+            previous_blockhash: String::new(),
+            blockhash: String::new(),
+            parent_slot: 0,
+            transactions,
+            rewards: vec![],
+            block_time: None,
+            block_height: Some(7),
+        }
+    }
+
+    #[test]
+    fn versioned_transaction_without_a_meta() {
+        let transaction = StoredConfirmedBlockTransaction {
+            transaction: VersionedTransaction {
+                signatures: vec![],
+                message: VersionedMessage::V0(v0::Message::default()),
+            },
+            meta: None,
+        };
+        assert!(matches!(
+            ConfirmedBlock::try_from(block(vec![transaction])),
+            Err(super::super::Error::MissingMeta)
+        ));
+    }
+
+    #[test]
+    fn blocks_before_block_height() {
+        let mut bytes = bincode::serialize(&block(vec![])).unwrap();
+        // Some(7) is a tag byte and a u64.
+        bytes.truncate(bytes.len() - 9);
+        let stored: StoredConfirmedBlock = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(stored.block_height, None);
+        let block = ConfirmedBlock::try_from(stored).unwrap();
+        assert_eq!(block.block_height, None);
+    }
+
+    #[test]
+    fn meta_without_an_error() {
+        let meta = TransactionStatusMeta::from(StoredConfirmedBlockTransactionStatusMeta {
+            err: None,
+            fee: 5000,
+            pre_balances: vec![1],
+            post_balances: vec![2],
+        });
+        assert_eq!(meta.status, Ok(()));
+        assert_eq!(
+            (meta.fee, meta.pre_balances, meta.post_balances),
+            (5000, vec![1], vec![2])
+        );
+    }
+}
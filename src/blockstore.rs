@@ -0,0 +1,130 @@
+#[cfg(feature = "blockstore")]
+use crate::legacy::limits::Limits;
+use crate::meta::{self, Decoded};
+use solana_sdk::clock::Slot;
+use solana_sdk::signature::Signature;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("a TransactionStatus key is {} bytes, not {0}", Key::LEN)]
+    KeyLength(usize),
+    #[error("{0}")]
+    Meta(#[from] meta::Error),
+    #[cfg(feature = "blockstore")]
+    #[error("rocksdb error: {0}")]
+    RocksDb(#[from] rocksdb::Error),
+    #[error("the blockstore has no {0} column family")]
+    MissingColumn(&'static str),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The column family holding the metas.
+// From https://github.com/solana-labs/solana/blob/v1.14.18/ledger/src/blockstore_db.rs
+pub const COLUMN: &str = "transaction_status";
+
+/// The key of a TransactionStatus entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    /// The primary index the entry was written under, 0 or 1.
+    pub index: u64,
+    pub signature: Signature,
+    pub slot: Slot,
+}
+
+impl Key {
+    pub const LEN: usize = 8 + 64 + 8;
+
+    // From https://github.com/solana-labs/solana/blob/v1.14.18/ledger/src/blockstore_db.rs
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != Self::LEN {
+            return Err(Error::KeyLength(bytes.len()));
+        }
+        let index = u64::from_be_bytes(bytes[0..8].try_into().unwrap());
+        let signature = Signature::new(&bytes[8..72]);
+        let slot = u64::from_be_bytes(bytes[72..80].try_into().unwrap());
+        Ok(Self {
+            index,
+            signature,
+            slot,
+        })
+    }
+}
+
+/// A TransactionStatus entry, with its value decoded if possible.
+#[derive(Debug)]
+pub struct Entry {
+    pub key: Key,
+    pub meta: Result<Decoded>,
+}
+
+/// A blockstore RocksDB directory, opened read-only.
+#[cfg(feature = "blockstore")]
+pub struct Blockstore {
+    db: rocksdb::DB,
+    limits: Limits,
+}
+
+#[cfg(feature = "blockstore")]
+impl Blockstore {
+    /// Opens the ledger's `rocksdb` directory, without writing to it, which
+    /// leaves a running validator undisturbed.
+    pub fn open(path: &std::path::Path, limits: Limits) -> Result<Self> {
+        let db = rocksdb::DB::open_cf_for_read_only(
+            &rocksdb::Options::default(),
+            path,
+            [COLUMN],
+            false,
+        )?;
+        Ok(Self { db, limits })
+    }
+
+    /// Every TransactionStatus entry, in key order. Values that do not decode
+    /// are in their entry, other errors are returned as such.
+    pub fn entries(&self) -> Result<impl Iterator<Item = Result<Entry>> + '_> {
+        let column = self
+            .db
+            .cf_handle(COLUMN)
+            .ok_or(Error::MissingColumn(COLUMN))?;
+        let entries = self
+            .db
+            .iterator_cf(column, rocksdb::IteratorMode::Start)
+            .map(move |item| {
+                let (key, value) = item?;
+                let key = Key::parse(&key)?;
+                let meta = meta::decode(&value, &self.limits).map_err(Into::into);
+                Ok(Entry { key, meta })
+            });
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_is_big_endian_index_signature_and_slot() {
+        let signature: Vec<u8> = (0..64).collect();
+        let mut bytes = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        bytes.extend_from_slice(&signature);
+        bytes.extend_from_slice(&[0, 0, 0, 0, 0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(
+            Key::parse(&bytes).unwrap(),
+            Key {
+                index: 1,
+                signature: Signature::new(&signature),
+                slot: 0x0102_0304,
+            }
+        );
+    }
+
+    #[test]
+    fn key_of_the_wrong_length() {
+        for len in [0, Key::LEN - 1, Key::LEN + 1] {
+            let err = Key::parse(&vec![0; len]).unwrap_err();
+            assert!(matches!(err, Error::KeyLength(found) if found == len));
+        }
+    }
+}
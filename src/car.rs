@@ -0,0 +1,678 @@
+use crate::legacy::limits::{Exceeded, Limits};
+use crate::meta::{self, Decoded};
+use ciborium::value::Value;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid CAR file at byte {0}: {1}")]
+    Car(u64, String),
+    #[error("invalid {0} node: {1}")]
+    Node(&'static str, String),
+    #[error("data frame {0} is not in the file before its transaction")]
+    MissingFrame(String),
+    #[error("data frames waiting for their transaction are over the limit of {0} bytes")]
+    FramesOverLimit(usize),
+    #[error("zstd error: {0}")]
+    Zstd(io::Error),
+    #[error("decode limit exceeded: {0}")]
+    Limit(#[from] Exceeded),
+    #[error("{0}")]
+    Meta(#[from] meta::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// From https://github.com/rpcpool/yellowstone-faithful/blob/main/ledger.ipldsch
+const KIND_TRANSACTION: i128 = 0;
+const KIND_DATA_FRAME: i128 = 6;
+
+/// Sections bigger than this are taken for corruption rather than allocated.
+const MAX_SECTION_LEN: u64 = 1 << 30;
+
+/// The default bound on the data frames held until their transaction.
+pub const DEFAULT_MAX_FRAME_BYTES: usize = 1 << 30;
+
+/// A block of a CAR file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    /// Where the section of the block starts in the file.
+    pub offset: u64,
+    pub cid: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// Reads the blocks of a CARv1 stream in order, without seeking.
+pub struct Reader<R> {
+    reader: R,
+    offset: u64,
+}
+
+impl<R: Read> Reader<R> {
+    /// Reads the header, which must be a version 1 one.
+    pub fn new(reader: R) -> Result<Self> {
+        let mut reader = Self { reader, offset: 0 };
+        let header = match reader.section()? {
+            Some(header) => header,
+            None => return Err(Error::Car(0, "the file is empty".to_string())),
+        };
+        let version = match ciborium::de::from_reader::<Value, _>(header.as_slice()) {
+            Ok(Value::Map(entries)) => entries.into_iter().find_map(|(key, value)| {
+                (key == Value::Text("version".to_string())).then(|| integer(&value))?
+            }),
+            _ => None,
+        };
+        if version != Some(1) {
+            return Err(Error::Car(0, "not a CARv1 header".to_string()));
+        }
+        Ok(reader)
+    }
+
+    /// The next block, or `None` at the end of the file.
+    pub fn next_block(&mut self) -> Result<Option<Block>> {
+        let offset = self.offset;
+        let section = match self.section()? {
+            Some(section) => section,
+            None => return Ok(None),
+        };
+        let cid_len = cid_len(&section).ok_or_else(|| Error::Car(offset, "invalid CID".into()))?;
+        let mut cid = section;
+        let data = cid.split_off(cid_len);
+        Ok(Some(Block { offset, cid, data }))
+    }
+
+    /// A length-prefixed section, or `None` at the end of the file.
+    fn section(&mut self) -> Result<Option<Vec<u8>>> {
+        let offset = self.offset;
+        let len = match self.varint()? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        if len > MAX_SECTION_LEN {
+            return Err(Error::Car(offset, format!("a section of {} bytes", len)));
+        }
+        // Read what is there rather than allocate what the length claims.
+        let mut section = Vec::new();
+        (&mut self.reader).take(len).read_to_end(&mut section)?;
+        if (section.len() as u64) < len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        self.offset += len;
+        Ok(Some(section))
+    }
+
+    /// An unsigned LEB128 varint, or `None` at the end of the file.
+    fn varint(&mut self) -> Result<Option<u64>> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let mut byte = [0];
+            if self.reader.read(&mut byte)? == 0 {
+                if shift == 0 {
+                    return Ok(None);
+                }
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+            self.offset += 1;
+            value |= u64::from(byte[0] & 0x7f) << shift;
+            if byte[0] & 0x80 == 0 {
+                return Ok(Some(value));
+            }
+        }
+        Err(Error::Car(self.offset, "varint overflow".to_string()))
+    }
+}
+
+/// The length of the CID at the start of `bytes`.
+fn cid_len(bytes: &[u8]) -> Option<usize> {
+    // CIDv0 is a bare sha2-256 multihash.
+    if bytes.starts_with(&[0x12, 0x20]) {
+        return (bytes.len() >= 34).then_some(34);
+    }
+    // CIDv1 is version, codec, hash function and digest length, then the digest.
+    let mut position = 0;
+    let mut digest_len = 0;
+    for _ in 0..4 {
+        let (value, len) = varint(&bytes[position..])?;
+        position += len;
+        digest_len = value as usize;
+    }
+    let end = position.checked_add(digest_len)?;
+    (end <= bytes.len()).then_some(end)
+}
+
+fn varint(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    for (position, byte) in bytes.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * position);
+        if byte & 0x80 == 0 {
+            return Some((value, position + 1));
+        }
+    }
+    None
+}
+
+fn integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Integer(integer) => Some(i128::from(*integer)),
+        _ => None,
+    }
+}
+
+/// A transaction node, with its meta decoded if possible.
+#[derive(Debug)]
+pub struct Transaction {
+    /// Where the section of the node starts in the file.
+    pub offset: u64,
+    pub cid: Vec<u8>,
+    pub slot: u64,
+    /// The position of the transaction in its block, when the node has it.
+    pub index: Option<u64>,
+    /// The bincode-encoded transaction, assembled from its data frames.
+    pub data: Result<Vec<u8>>,
+    /// `None` for transactions stored without a meta.
+    pub meta: Result<Option<Decoded>>,
+}
+
+/// A DataFrame node: a part of a buffer, continued by the frames it links to.
+struct DataFrame {
+    data: Vec<u8>,
+    next: Vec<Vec<u8>>,
+}
+
+impl DataFrame {
+    /// The bytes it takes while held.
+    fn size(&self) -> usize {
+        self.data.len() + self.next.iter().map(Vec::len).sum::<usize>()
+    }
+}
+
+/// Walks the transaction nodes of an Old Faithful CAR file.
+///
+/// Buffers too big for one node continue in DataFrame nodes, which are
+/// written before the transaction linking to them and kept until then, up to
+/// a total of `max_frame_bytes`.
+pub struct Transactions<R> {
+    reader: Reader<R>,
+    limits: Limits,
+    frames: HashMap<Vec<u8>, DataFrame>,
+    /// The sizes of `frames` summed up.
+    frame_bytes: usize,
+    max_frame_bytes: usize,
+}
+
+impl<R: Read> Transactions<R> {
+    pub fn new(reader: R, limits: Limits) -> Result<Self> {
+        Ok(Self {
+            reader: Reader::new(reader)?,
+            limits,
+            frames: HashMap::new(),
+            frame_bytes: 0,
+            max_frame_bytes: DEFAULT_MAX_FRAME_BYTES,
+        })
+    }
+
+    /// Bounds the data frames held until their transaction, which frames no
+    /// transaction links to would otherwise grow for the whole file.
+    pub fn max_frame_bytes(mut self, max_frame_bytes: usize) -> Self {
+        self.max_frame_bytes = max_frame_bytes;
+        self
+    }
+
+    /// The next transaction node. Errors reading the file, going over the
+    /// frame limit or a transaction node without a slot end the walk, errors
+    /// within a node are in its `data` or `meta`.
+    pub fn next_transaction(&mut self) -> Result<Option<Transaction>> {
+        while let Some(block) = self.reader.next_block()? {
+            let node = match ciborium::de::from_reader::<Value, _>(block.data.as_slice()) {
+                Ok(Value::Array(fields)) => fields,
+                // Not DAG-CBOR nodes of the known kinds, so not ours to report.
+                _ => continue,
+            };
+            match node.first().and_then(integer) {
+                Some(KIND_DATA_FRAME) => {
+                    if let Ok(frame) = data_frame(&node) {
+                        self.hold(block.cid, frame)?;
+                    }
+                }
+                Some(KIND_TRANSACTION) => return self.transaction(block, &node).map(Some),
+                _ => (),
+            }
+        }
+        Ok(None)
+    }
+
+    fn hold(&mut self, cid: Vec<u8>, frame: DataFrame) -> Result<()> {
+        self.frame_bytes += frame.size();
+        if let Some(replaced) = self.frames.insert(cid, frame) {
+            self.frame_bytes -= replaced.size();
+        }
+        if self.frame_bytes > self.max_frame_bytes {
+            return Err(Error::FramesOverLimit(self.max_frame_bytes));
+        }
+        Ok(())
+    }
+
+    // type Transaction struct { kind Int, data DataFrame, metadata DataFrame, slot Int, index optional Int }
+    fn transaction(&mut self, block: Block, node: &[Value]) -> Result<Transaction> {
+        let slot = match node.get(3).and_then(integer) {
+            Some(slot) => slot as u64,
+            None => return Err(Error::Node("transaction", "no slot".to_string())),
+        };
+        let index = node.get(4).and_then(integer).map(|index| index as u64);
+        let data = match node.get(1) {
+            Some(Value::Array(frame)) => data_frame(frame).and_then(|frame| self.assemble(frame)),
+            _ => Err(Error::Node("transaction", "no data frame".to_string())),
+        };
+        let meta = match node.get(2) {
+            Some(Value::Array(frame)) => data_frame(frame)
+                .and_then(|frame| self.assemble(frame))
+                .and_then(|compressed| self.meta(&compressed)),
+            _ => Err(Error::Node("transaction", "no metadata frame".to_string())),
+        };
+        Ok(Transaction {
+            offset: block.offset,
+            cid: block.cid,
+            slot,
+            index,
+            data,
+            meta,
+        })
+    }
+
+    /// The whole buffer starting with `frame`, taking its continuations out of
+    /// the frames seen so far, depth first.
+    fn assemble(&mut self, frame: DataFrame) -> Result<Vec<u8>> {
+        let mut data = frame.data;
+        let mut pending: Vec<Vec<u8>> = frame.next.into_iter().rev().collect();
+        while let Some(cid) = pending.pop() {
+            let next = self
+                .frames
+                .remove(&cid)
+                .ok_or_else(|| Error::MissingFrame(hex::encode(&cid)))?;
+            self.frame_bytes -= next.size();
+            data.extend(next.data);
+            pending.extend(next.next.into_iter().rev());
+        }
+        Ok(data)
+    }
+
+    /// Metas are zstd-compressed, then protobuf or bincode.
+    fn meta(&self, compressed: &[u8]) -> Result<Option<Decoded>> {
+        if compressed.is_empty() {
+            return Ok(None);
+        }
+        let max_bytes = self.limits.max_bytes;
+        let mut bytes = Vec::new();
+        zstd::stream::read::Decoder::new(compressed)
+            .map_err(Error::Zstd)?
+            .take(max_bytes as u64 + 1)
+            .read_to_end(&mut bytes)
+            .map_err(Error::Zstd)?;
+        if bytes.len() > max_bytes {
+            return Err(Exceeded::Bytes(bytes.len(), max_bytes).into());
+        }
+        Ok(Some(meta::decode(&bytes, &self.limits)?))
+    }
+}
+
+impl<R: Read> Iterator for Transactions<R> {
+    type Item = Result<Transaction>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_transaction().transpose()
+    }
+}
+
+// type DataFrame struct { kind Int, hash optional Int, index optional Int, total optional Int, data Buffer, next optional List_Link }
+fn data_frame(node: &[Value]) -> Result<DataFrame> {
+    let invalid = |message: &str| Error::Node("data frame", message.to_string());
+    let data = match node.get(4) {
+        Some(Value::Bytes(data)) => data.clone(),
+        _ => return Err(invalid("no data")),
+    };
+    let next = match node.get(5) {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(links)) => links
+            .iter()
+            .map(|link| match link {
+                // DAG-CBOR links are tag 42 over the CID behind a 0x00 multibase prefix.
+                Value::Tag(42, cid) => match cid.as_ref() {
+                    Value::Bytes(cid) if cid.first() == Some(&0) => Ok(cid[1..].to_vec()),
+                    _ => Err(invalid("invalid link")),
+                },
+                _ => Err(invalid("invalid link")),
+            })
+            .collect::<Result<_>>()?,
+        Some(_) => return Err(invalid("invalid next links")),
+    };
+    Ok(DataFrame { data, next })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::ce598;
+    use crate::meta::Encoding;
+
+    fn leb128(mut value: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn cbor(value: &Value) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// A DAG-CBOR sha2-256 CIDv1, told apart by `n`.
+    fn cid(n: u32) -> Vec<u8> {
+        let mut cid = vec![0x01, 0x71, 0x12, 0x20];
+        cid.extend_from_slice(&n.to_be_bytes());
+        cid.resize(4 + 32, 0);
+        cid
+    }
+
+    fn int(value: u64) -> Value {
+        Value::Integer(value.into())
+    }
+
+    fn frame(data: &[u8], next: &[Vec<u8>]) -> Value {
+        let links = next
+            .iter()
+            .map(|cid| Value::Tag(42, Box::new(Value::Bytes([&[0][..], cid].concat()))))
+            .collect();
+        Value::Array(vec![
+            int(KIND_DATA_FRAME as u64),
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            Value::Bytes(data.to_vec()),
+            Value::Array(links),
+        ])
+    }
+
+    fn transaction(metadata: Value, slot: Option<u64>) -> Value {
+        Value::Array(vec![
+            int(KIND_TRANSACTION as u64),
+            frame(b"transaction", &[]),
+            metadata,
+            slot.map_or(Value::Null, int),
+            int(7),
+        ])
+    }
+
+    fn car(blocks: &[(Vec<u8>, Value)]) -> Vec<u8> {
+        let header = Value::Map(vec![
+            (Value::Text("version".to_string()), int(1)),
+            (Value::Text("roots".to_string()), Value::Array(vec![])),
+        ]);
+        let header = cbor(&header);
+        let mut bytes = leb128(header.len() as u64);
+        bytes.extend(header);
+        for (cid, node) in blocks {
+            let section = [cid.clone(), cbor(node)].concat();
+            bytes.extend(leb128(section.len() as u64));
+            bytes.extend(section);
+        }
+        bytes
+    }
+
+    fn meta() -> ce598::TransactionStatusMeta {
+        ce598::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![1_000_000; 40],
+            post_balances: vec![995_000; 40],
+            inner_instructions: Some(vec![]),
+        }
+    }
+
+    fn compressed_meta() -> Vec<u8> {
+        let bytes = ce598::to_bincode(&meta()).unwrap();
+        zstd::stream::encode_all(bytes.as_slice(), 0).unwrap()
+    }
+
+    fn walk(bytes: &[u8], limits: Limits) -> Result<Vec<Transaction>> {
+        Transactions::new(bytes, limits)?.collect()
+    }
+
+    #[test]
+    fn varints() {
+        assert_eq!(varint(&[0x00]), Some((0, 1)));
+        assert_eq!(varint(&[0x7f, 0xff]), Some((127, 1)));
+        assert_eq!(varint(&[0xac, 0x02]), Some((300, 2)));
+        assert_eq!(varint(&[0x80]), None);
+        assert_eq!(varint(&[0xff; 11]), None);
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let bytes = leb128(value);
+            assert_eq!(varint(&bytes), Some((value, bytes.len())));
+        }
+    }
+
+    #[test]
+    fn cid_lengths() {
+        let v0 = [&[0x12, 0x20][..], &[0xaa; 32]].concat();
+        assert_eq!(cid_len(&[v0.as_slice(), b"data"].concat()), Some(34));
+        assert_eq!(cid_len(&v0[..33]), None);
+        let v1 = cid(1);
+        assert_eq!(cid_len(&[v1.as_slice(), b"data"].concat()), Some(36));
+        assert_eq!(cid_len(&v1[..35]), None);
+        assert_eq!(cid_len(&[0x01, 0x71]), None);
+    }
+
+    #[test]
+    fn header_must_be_carv1() {
+        assert!(matches!(Reader::new(&[][..]), Err(Error::Car(0, _))));
+        let header = cbor(&Value::Map(vec![(
+            Value::Text("version".to_string()),
+            int(2),
+        )]));
+        let bytes = [leb128(header.len() as u64), header].concat();
+        assert!(matches!(
+            Reader::new(bytes.as_slice()),
+            Err(Error::Car(0, _))
+        ));
+    }
+
+    #[test]
+    fn blocks_in_order_with_their_offsets() {
+        let bytes = car(&[(cid(1), int(1)), (cid(2), int(2))]);
+        let mut reader = Reader::new(bytes.as_slice()).unwrap();
+        let first = reader.next_block().unwrap().unwrap();
+        let second = reader.next_block().unwrap().unwrap();
+        assert_eq!(reader.next_block().unwrap(), None);
+        assert_eq!((first.cid, first.data), (cid(1), cbor(&int(1))));
+        assert_eq!((second.cid, second.data), (cid(2), cbor(&int(2))));
+        let first_len = 1 + 36 + cbor(&int(1)).len() as u64;
+        assert_eq!(second.offset, first.offset + first_len);
+    }
+
+    #[test]
+    fn truncated_section() {
+        let bytes = car(&[(cid(1), int(1))]);
+        let mut reader = Reader::new(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(matches!(reader.next_block(), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn section_longer_than_the_file() {
+        let mut bytes = car(&[]);
+        bytes.extend(leb128(MAX_SECTION_LEN));
+        bytes.extend(cid(1));
+        let mut reader = Reader::new(bytes.as_slice()).unwrap();
+        match reader.next_block() {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn data_frame_links() {
+        let node = frame(b"abc", &[cid(1), cid(2)]);
+        let Value::Array(node) = node else {
+            unreachable!()
+        };
+        let frame = data_frame(&node).unwrap();
+        assert_eq!(frame.data, b"abc");
+        assert_eq!(frame.next, vec![cid(1), cid(2)]);
+
+        let mut bad = node.clone();
+        bad[5] = Value::Array(vec![Value::Tag(42, Box::new(Value::Bytes(cid(1))))]);
+        assert!(matches!(data_frame(&bad), Err(Error::Node(..))));
+        bad.truncate(4);
+        assert!(matches!(data_frame(&bad), Err(Error::Node(..))));
+    }
+
+    #[test]
+    fn meta_split_across_frames() {
+        let compressed = compressed_meta();
+        let (first, rest) = compressed.split_at(compressed.len() / 3);
+        let (second, third) = rest.split_at(rest.len() / 2);
+        // The transaction's frame links to 1, which links to 2.
+        let bytes = car(&[
+            (cid(2), frame(third, &[])),
+            (cid(1), frame(second, &[cid(2)])),
+            (cid(3), transaction(frame(first, &[cid(1)]), Some(42))),
+        ]);
+        let transactions = walk(&bytes, Limits::default()).unwrap();
+        assert_eq!(transactions.len(), 1);
+        let transaction = &transactions[0];
+        assert_eq!((transaction.slot, transaction.index), (42, Some(7)));
+        assert_eq!(transaction.cid, cid(3));
+        assert_eq!(transaction.data.as_ref().unwrap(), b"transaction");
+        let decoded = transaction.meta.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(decoded.encoding, Encoding::Bincode);
+        assert_eq!(decoded.meta.fee, meta().fee);
+        assert_eq!(decoded.meta.pre_balances, meta().pre_balances);
+    }
+
+    #[test]
+    fn long_frame_chain() {
+        let compressed = compressed_meta();
+        let chain = 100_000u32;
+        let mut blocks = Vec::new();
+        for n in (1..=chain).rev() {
+            let next = if n == chain { vec![] } else { vec![cid(n + 1)] };
+            let data = if n == chain {
+                &compressed[1..]
+            } else {
+                &[][..]
+            };
+            blocks.push((cid(n), frame(data, &next)));
+        }
+        blocks.push((
+            cid(0),
+            transaction(frame(&compressed[..1], &[cid(1)]), Some(1)),
+        ));
+        let transactions = walk(&car(&blocks), Limits::default()).unwrap();
+        let decoded = transactions[0].meta.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(decoded.meta.fee, meta().fee);
+    }
+
+    #[test]
+    fn missing_frame() {
+        let bytes = car(&[(cid(3), transaction(frame(b"", &[cid(1)]), Some(1)))]);
+        let transactions = walk(&bytes, Limits::default()).unwrap();
+        assert!(matches!(transactions[0].meta, Err(Error::MissingFrame(_))));
+    }
+
+    #[test]
+    fn corrupt_transaction_frame() {
+        let meta = frame(&compressed_meta(), &[]);
+        let mut missing = transaction(meta.clone(), Some(1));
+        let mut invalid = missing.clone();
+        if let (Value::Array(missing), Value::Array(invalid)) = (&mut missing, &mut invalid) {
+            missing[1] = frame(b"trans", &[cid(1)]);
+            invalid[1] = Value::Array(vec![int(KIND_DATA_FRAME as u64)]);
+        }
+        let bytes = car(&[(cid(3), missing), (cid(4), invalid)]);
+        let transactions = walk(&bytes, Limits::default()).unwrap();
+        assert!(matches!(transactions[0].data, Err(Error::MissingFrame(_))));
+        assert!(matches!(
+            transactions[1].data,
+            Err(Error::Node("data frame", _))
+        ));
+        // The meta does not depend on the transaction's frames.
+        assert!(transactions
+            .iter()
+            .all(|transaction| transaction.meta.is_ok()));
+    }
+
+    #[test]
+    fn no_meta() {
+        let bytes = car(&[(cid(3), transaction(frame(b"", &[]), Some(1)))]);
+        let transactions = walk(&bytes, Limits::default()).unwrap();
+        assert!(matches!(transactions[0].meta, Ok(None)));
+    }
+
+    #[test]
+    fn no_slot() {
+        let bytes = car(&[(cid(3), transaction(frame(&compressed_meta(), &[]), None))]);
+        let err = walk(&bytes, Limits::default()).unwrap_err();
+        assert!(matches!(err, Error::Node("transaction", _)));
+    }
+
+    #[test]
+    fn decompressed_meta_over_the_byte_limit() {
+        let bomb = zstd::stream::encode_all(&[0u8; 1 << 20][..], 0).unwrap();
+        let bytes = car(&[(cid(3), transaction(frame(&bomb, &[]), Some(1)))]);
+        let limits = Limits {
+            max_bytes: 1 << 10,
+            ..Limits::default()
+        };
+        let transactions = walk(&bytes, limits).unwrap();
+        assert!(matches!(
+            transactions[0].meta,
+            Err(Error::Limit(Exceeded::Bytes(1025, 1024)))
+        ));
+    }
+
+    #[test]
+    fn unclaimed_frames_over_the_limit() {
+        let blocks: Vec<_> = (0..10).map(|n| (cid(n), frame(&[0; 100], &[]))).collect();
+        let bytes = car(&blocks);
+        let transactions = Transactions::new(bytes.as_slice(), Limits::default()).unwrap();
+        let err = transactions
+            .max_frame_bytes(500)
+            .collect::<Result<Vec<_>>>()
+            .unwrap_err();
+        assert!(matches!(err, Error::FramesOverLimit(500)));
+    }
+
+    #[test]
+    fn claimed_frames_are_released() {
+        // Each transaction claims its frame, so at most one is held at a time.
+        let compressed = compressed_meta();
+        let mut blocks = Vec::new();
+        for n in 0..10 {
+            blocks.push((cid(2 * n), frame(&compressed, &[])));
+            blocks.push((
+                cid(2 * n + 1),
+                transaction(frame(b"", &[cid(2 * n)]), Some(1)),
+            ));
+        }
+        let bytes = car(&blocks);
+        let transactions = Transactions::new(bytes.as_slice(), Limits::default())
+            .unwrap()
+            .max_frame_bytes(compressed.len())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(transactions.len(), 10);
+        assert!(transactions
+            .iter()
+            .all(|transaction| transaction.meta.is_ok()));
+    }
+}
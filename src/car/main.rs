@@ -0,0 +1,96 @@
+use demo_serde_txmeta::car::Transactions;
+use demo_serde_txmeta::legacy::limits::Limits;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+
+// Walks Old Faithful CAR files and writes one NDJSON record per transaction,
+// with the version its meta was decoded as or why it was not.
+fn main() {
+    let files: Vec<String> = std::env::args().skip(1).collect();
+    if files.is_empty() {
+        eprintln!("usage: car <file.car>...");
+        std::process::exit(2);
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let mut versions: BTreeMap<String, u64> = BTreeMap::new();
+    let (mut failed, mut unreadable) = (0, 0);
+    for file in &files {
+        let result = File::open(file)
+            .map_err(Into::into)
+            .and_then(|reader| Transactions::new(BufReader::new(reader), Limits::default()));
+        let transactions = match result {
+            Ok(transactions) => transactions,
+            Err(e) => {
+                eprintln!("error: {}: {}", file, e);
+                unreadable += 1;
+                continue;
+            }
+        };
+        for transaction in transactions {
+            let transaction = match transaction {
+                Ok(transaction) => transaction,
+                Err(e) => {
+                    eprintln!("error: {}: {}", file, e);
+                    unreadable += 1;
+                    break;
+                }
+            };
+            let mut record = json!({
+                "source": format!("{}@{}", file, transaction.offset),
+                "slot": transaction.slot,
+                "index": transaction.index,
+            });
+            if let Err(e) = &transaction.data {
+                failed += 1;
+                record["data_error"] = json!(e.to_string());
+            }
+            let version = match &transaction.meta {
+                Ok(Some(decoded)) => {
+                    record["encoding"] = json!(decoded.encoding.to_string());
+                    record["version"] = json!(decoded.version.to_string());
+                    format!("{} {}", decoded.encoding, decoded.version)
+                }
+                Ok(None) => "none".to_string(),
+                Err(e) => {
+                    failed += 1;
+                    record["error"] = json!(e.to_string());
+                    "error".to_string()
+                }
+            };
+            *versions.entry(version).or_default() += 1;
+            let written = serde_json::to_writer(&mut out, &record)
+                .map_err(io::Error::from)
+                .and_then(|()| writeln!(out));
+            if let Err(e) = written {
+                exit_on_write_error(e);
+            }
+        }
+    }
+    if let Err(e) = out.flush() {
+        exit_on_write_error(e);
+    }
+
+    for (version, count) in &versions {
+        eprintln!("{}: {}", version, count);
+    }
+    if unreadable > 0 {
+        std::process::exit(2);
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Ends the process on an output error, quietly when the reader went away as
+/// `car ... | head` does.
+fn exit_on_write_error(e: io::Error) -> ! {
+    if e.kind() == io::ErrorKind::BrokenPipe {
+        std::process::exit(0);
+    }
+    eprintln!("error: writing output: {}", e);
+    std::process::exit(2);
+}
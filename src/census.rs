@@ -0,0 +1,314 @@
+use crate::batch::{self, Input};
+use crate::bigtable;
+use crate::legacy::limits::{self, Limits};
+use crate::meta::{self, Decoded};
+use serde::Serialize;
+use solana_sdk::transaction::TransactionError;
+use solana_storage_proto::StoredTransactionStatusMeta;
+use std::collections::BTreeMap;
+
+/// What a corpus of metas is made of.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Census {
+    pub blobs: u64,
+    pub decoded: u64,
+    pub failed: u64,
+    /// `decoded` and `failed` as fractions of `blobs`, 0 without blobs.
+    pub decoded_rate: f64,
+    pub failed_rate: f64,
+    /// Decoded metas by `<encoding> <version>`.
+    pub versions: BTreeMap<String, u64>,
+    /// Decoded metas by the variant of their error, `Ok` when there is none.
+    pub transaction_errors: BTreeMap<String, u64>,
+    /// `InstructionError` metas by the variant of the instruction error.
+    pub instruction_errors: BTreeMap<String, u64>,
+    pub fees: Fees,
+    /// Decoded metas by their number of inner instructions, when recorded.
+    pub inner_instructions: BTreeMap<usize, u64>,
+    /// Decoded metas that do not record inner instructions.
+    pub without_inner_instructions: u64,
+    pub failures: Vec<Failure>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Fees {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub total: u128,
+    /// Fees by order of magnitude: 0, then 1 for 1-9, 10 for 10-99, and so on.
+    pub magnitudes: BTreeMap<u64, u64>,
+}
+
+/// A blob that did not decode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Failure {
+    pub source: String,
+    /// How far into the blob bincode got reading it as
+    /// `StoredTransactionStatusMeta`, the last layout tried, when it did.
+    pub offset: Option<usize>,
+    pub error: String,
+}
+
+impl Census {
+    /// Decodes and counts the blobs of `file`, read the way `batch::read`
+    /// does, taking them out of the BigTable compression envelope first when
+    /// `compressed`.
+    pub fn add_file(&mut self, input: Input, file: &str, compressed: bool, limits: &Limits) {
+        batch::read(input, file, limits, &mut |source, blob| {
+            let blob = match blob {
+                Ok(blob) => blob,
+                Err(e) => return self.add_failure(source, None, e.to_string()),
+            };
+            if !compressed {
+                return self.add(source, &blob, limits);
+            }
+            match bigtable::decompress(&blob, limits) {
+                Ok(blob) => self.add(source, &blob, limits),
+                Err(e) => self.add_failure(source, None, e.to_string()),
+            }
+        });
+    }
+
+    /// Decodes and counts the blob read from `source`.
+    pub fn add(&mut self, source: String, bytes: &[u8], limits: &Limits) {
+        match meta::decode(bytes, limits) {
+            Ok(decoded) => self.add_decoded(&decoded),
+            Err(e) => {
+                // Only undecodable blobs got as far as bincode.
+                let offset = match e {
+                    meta::Error::Undecodable { .. } => {
+                        limits::failure_offset::<StoredTransactionStatusMeta>(bytes, limits)
+                    }
+                    _ => None,
+                };
+                self.add_failure(source, offset, e.to_string());
+            }
+        }
+    }
+
+    pub fn add_decoded(&mut self, decoded: &Decoded) {
+        self.blobs += 1;
+        self.decoded += 1;
+        self.update_rates();
+        let version = format!("{} {}", decoded.encoding, decoded.version);
+        *self.versions.entry(version).or_default() += 1;
+
+        let meta = &decoded.meta;
+        let error = match &meta.status {
+            Ok(()) => "Ok".to_string(),
+            Err(e) => variant(e),
+        };
+        *self.transaction_errors.entry(error).or_default() += 1;
+        if let Err(TransactionError::InstructionError(_, e)) = &meta.status {
+            *self.instruction_errors.entry(variant(e)).or_default() += 1;
+        }
+
+        let fees = &mut self.fees;
+        fees.min = Some(fees.min.map_or(meta.fee, |min| min.min(meta.fee)));
+        fees.max = Some(fees.max.map_or(meta.fee, |max| max.max(meta.fee)));
+        fees.total += u128::from(meta.fee);
+        let magnitude = match meta.fee {
+            0 => 0,
+            fee => 10u64.pow(fee.ilog10()),
+        };
+        *fees.magnitudes.entry(magnitude).or_default() += 1;
+
+        match &meta.inner_instructions {
+            Some(inner) => {
+                let count = inner.iter().map(|inner| inner.instructions.len()).sum();
+                *self.inner_instructions.entry(count).or_default() += 1;
+            }
+            None => self.without_inner_instructions += 1,
+        }
+    }
+
+    /// Counts a blob that did not decode, or could not be read.
+    pub fn add_failure(&mut self, source: String, offset: Option<usize>, error: String) {
+        self.blobs += 1;
+        self.failed += 1;
+        self.update_rates();
+        self.failures.push(Failure {
+            source,
+            offset,
+            error,
+        });
+    }
+
+    fn update_rates(&mut self) {
+        let blobs = self.blobs as f64;
+        self.decoded_rate = self.decoded as f64 / blobs;
+        self.failed_rate = self.failed as f64 / blobs;
+    }
+}
+
+/// The name of the variant of an enum, the way serde writes it.
+fn variant<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(serde_json::Value::Object(map)) if map.len() == 1 => map.keys().next().unwrap().clone(),
+        _ => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::{b7b4, ce598};
+
+    fn b7b4(fee: u64) -> Vec<u8> {
+        let meta = b7b4::TransactionStatusMeta {
+            status: Ok(()),
+            fee,
+            pre_balances: vec![10_000],
+            post_balances: vec![10_000 - fee.min(10_000)],
+        };
+        b7b4::to_bincode(&meta).unwrap()
+    }
+
+    fn ce598(
+        status: ce598::Result<()>,
+        inner_instructions: Option<Vec<ce598::InnerInstructions>>,
+    ) -> Vec<u8> {
+        let meta = ce598::TransactionStatusMeta {
+            status,
+            fee: 5000,
+            pre_balances: vec![10_000],
+            post_balances: vec![5000],
+            inner_instructions,
+        };
+        ce598::to_bincode(&meta).unwrap()
+    }
+
+    fn inner(instructions: usize) -> ce598::InnerInstructions {
+        let instruction = ce598::CompiledInstruction {
+            program_id_index: 0,
+            accounts: vec![],
+            data: vec![],
+        };
+        ce598::InnerInstructions {
+            index: 0,
+            instructions: vec![instruction; instructions],
+        }
+    }
+
+    fn census(blobs: &[Vec<u8>]) -> Census {
+        let mut census = Census::default();
+        for (n, blob) in blobs.iter().enumerate() {
+            census.add(n.to_string(), blob, &Limits::default());
+        }
+        census
+    }
+
+    #[test]
+    fn versions() {
+        let census = census(&[b7b4(1), b7b4(1), ce598(Ok(()), None)]);
+        let versions: Vec<_> = census.versions.into_iter().collect();
+        assert_eq!(
+            versions,
+            vec![
+                (format!("bincode {}", b7b4::COMMIT), 2),
+                (format!("bincode {}", ce598::COMMIT), 1),
+            ]
+        );
+        assert_eq!((census.blobs, census.decoded, census.failed), (3, 3, 0));
+    }
+
+    #[test]
+    fn error_histograms() {
+        let custom =
+            ce598::TransactionError::InstructionError(0, ce598::InstructionError::Custom(1));
+        let census = census(&[
+            ce598(Ok(()), None),
+            ce598(Err(custom.clone()), None),
+            ce598(Err(custom), None),
+            ce598(Err(ce598::TransactionError::AccountInUse), None),
+        ]);
+        let errors: Vec<_> = census.transaction_errors.into_iter().collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("AccountInUse".to_string(), 1),
+                ("InstructionError".to_string(), 2),
+                ("Ok".to_string(), 1),
+            ]
+        );
+        let errors: Vec<_> = census.instruction_errors.into_iter().collect();
+        assert_eq!(errors, vec![("Custom".to_string(), 2)]);
+    }
+
+    #[test]
+    fn fee_magnitudes() {
+        let census = census(&[b7b4(0), b7b4(1), b7b4(9), b7b4(10), b7b4(u64::MAX)]);
+        let magnitudes: Vec<_> = census.fees.magnitudes.into_iter().collect();
+        assert_eq!(
+            magnitudes,
+            vec![(0, 1), (1, 2), (10, 1), (10_000_000_000_000_000_000, 1)]
+        );
+        assert_eq!(census.fees.min, Some(0));
+        assert_eq!(census.fees.max, Some(u64::MAX));
+        assert_eq!(census.fees.total, u128::from(u64::MAX) + 20);
+    }
+
+    #[test]
+    fn inner_instruction_counts() {
+        let census = census(&[
+            b7b4(1),
+            ce598(Ok(()), None),
+            ce598(Ok(()), Some(vec![])),
+            ce598(Ok(()), Some(vec![inner(2), inner(1)])),
+        ]);
+        let counts: Vec<_> = census.inner_instructions.into_iter().collect();
+        assert_eq!(counts, vec![(0, 1), (3, 1)]);
+        assert_eq!(census.without_inner_instructions, 2);
+    }
+
+    #[test]
+    fn failures_and_rates() {
+        let mut truncated = b7b4(1);
+        truncated.truncate(10);
+        let census = census(&[b7b4(1), vec![], truncated]);
+        assert_eq!((census.blobs, census.decoded, census.failed), (3, 1, 2));
+        assert_eq!(census.decoded_rate, 1.0 / 3.0);
+        assert_eq!(census.failed_rate, 2.0 / 3.0);
+
+        assert_eq!(census.failures[0].source, "1");
+        assert_eq!(census.failures[0].offset, None);
+        assert_eq!(census.failures[0].error, "empty blob");
+        assert_eq!(census.failures[1].source, "2");
+        assert_eq!(census.failures[1].offset, Some(10));
+    }
+
+    #[test]
+    fn no_blobs() {
+        let census = Census::default();
+        assert_eq!((census.decoded_rate, census.failed_rate), (0.0, 0.0));
+    }
+
+    #[test]
+    fn files_of_compressed_blobs() {
+        let envelope = |blob: Vec<u8>| hex::encode([&[0, 0, 0, 0][..], &blob].concat());
+        let file = std::env::temp_dir().join(format!("census-{}", std::process::id()));
+        std::fs::write(
+            &file,
+            format!("{}\n{}\nzz\n", envelope(b7b4(1)), envelope(vec![])),
+        )
+        .unwrap();
+        let mut census = Census::default();
+        let path = file.to_str().unwrap();
+        census.add_file(Input::Hex, path, true, &Limits::default());
+        std::fs::remove_file(&file).unwrap();
+
+        assert_eq!((census.blobs, census.decoded, census.failed), (3, 1, 2));
+        let sources: Vec<_> = census
+            .failures
+            .iter()
+            .map(|failure| &failure.source)
+            .collect();
+        assert_eq!(
+            sources,
+            vec![&format!("{}:2", path), &format!("{}:3", path)]
+        );
+        assert_eq!(census.failures[0].error, "empty blob");
+        assert!(census.failures[1].error.starts_with("invalid hex"));
+    }
+}
@@ -0,0 +1,41 @@
+use demo_serde_txmeta::schema::{compat, Schema};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 2 {
+        eprintln!("usage: compat <schema or commit>... (oldest first)");
+        std::process::exit(2);
+    }
+    let mut schemas = Vec::new();
+    for arg in &args {
+        match Schema::load(arg) {
+            Ok(schema) => schemas.push(schema),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let reports = compat::check_all(&schemas);
+    let mut compatible = true;
+    for report in &reports {
+        println!(
+            "{} -> {}: {}",
+            report.old,
+            report.new,
+            if report.is_compatible() {
+                "compatible"
+            } else {
+                "INCOMPATIBLE"
+            }
+        );
+        for finding in &report.findings {
+            println!("  {}", finding);
+        }
+        compatible &= report.is_compatible();
+    }
+    if !compatible {
+        std::process::exit(1);
+    }
+}
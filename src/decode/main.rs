@@ -0,0 +1,96 @@
+use demo_serde_txmeta::batch::{self, Decoder, Input};
+use demo_serde_txmeta::legacy::limits::Limits;
+use std::io::{self, BufWriter, Write};
+
+const USAGE: &str = "\
+usage: decode [--input=raw|hex|base64|base58] [--compressed] [--validate] [file]... (- or nothing for stdin)
+
+--validate checks each meta on its own: without the transaction, the rules
+against its account keys and instructions are not checked.";
+
+// Decodes legacy blobs of any known layout and writes one NDJSON record per blob.
+fn main() {
+    let mut input = Input::default();
+    let mut compressed = false;
+    let mut validate = false;
+    let mut files = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.split_once('=') {
+            Some(("--input", format)) => match format.parse() {
+                Ok(format) => input = format,
+                Err(e) => {
+                    eprintln!("error: {}\n{}", e, USAGE);
+                    std::process::exit(2);
+                }
+            },
+            _ if arg == "--compressed" => compressed = true,
+            _ if arg == "--validate" => validate = true,
+            _ if arg.starts_with("--") => {
+                eprintln!("error: unknown argument {:?}\n{}", arg, USAGE);
+                std::process::exit(2);
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        files.push("-".to_string());
+    }
+    let limits = Limits::default();
+    let mut decoder = match Decoder::new(limits) {
+        Ok(decoder) => decoder,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    if compressed {
+        decoder = decoder.compressed();
+    }
+    if validate {
+        decoder = decoder.validate();
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let (mut total, mut failed, mut invalid) = (0, 0, 0);
+    for file in &files {
+        batch::read(input, file, &limits, &mut |source, blob| {
+            let record = decoder.record(source, blob);
+            total += 1;
+            if record.error.is_some() {
+                failed += 1;
+            }
+            if !record.violations.is_empty() {
+                invalid += 1;
+            }
+            let written = serde_json::to_writer(&mut out, &record)
+                .map_err(io::Error::from)
+                .and_then(|()| writeln!(out));
+            if let Err(e) = written {
+                exit_on_write_error(e);
+            }
+        });
+    }
+    if let Err(e) = out.flush() {
+        exit_on_write_error(e);
+    }
+    if failed > 0 {
+        eprintln!("{} of {} blobs failed to decode", failed, total);
+    }
+    if invalid > 0 {
+        eprintln!("{} of {} blobs break validation rules", invalid, total);
+    }
+    if failed > 0 || invalid > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Ends the process on an output error, quietly when the reader went away as
+/// `decode ... | head` does.
+fn exit_on_write_error(e: io::Error) -> ! {
+    if e.kind() == io::ErrorKind::BrokenPipe {
+        std::process::exit(0);
+    }
+    eprintln!("error: writing output: {}", e);
+    std::process::exit(2);
+}
@@ -0,0 +1,28 @@
+use demo_serde_txmeta::schema::{diff, Schema};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() != 2 {
+        eprintln!("usage: diff <old schema or commit> <new schema or commit>");
+        std::process::exit(2);
+    }
+    let load = |arg: &str| match Schema::load(arg) {
+        Ok(schema) => schema,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    let old = load(&args[0]);
+    let new = load(&args[1]);
+
+    let changes = diff::diff(&old.registry, &new.registry);
+    println!("--- {}", old.commit);
+    println!("+++ {}", new.commit);
+    for change in &changes {
+        println!("{}", change);
+    }
+    if !changes.is_empty() {
+        std::process::exit(1);
+    }
+}
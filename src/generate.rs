@@ -0,0 +1,286 @@
+pub mod b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4;
+pub mod ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb;
+
+use crate::golang::enums::{Enums, Units};
+use crate::golang::module::Module;
+use crate::legacy::limits::Limits;
+use crate::schema::{Schema, Source};
+use crate::vectors::{self, Fixtures};
+use crate::{golang, json_schema, legacy, proto};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_reflection::Registry;
+use similar::TextDiff;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("tracing error: {0}")]
+    Trace(#[from] serde_reflection::Error),
+    #[error("samples error: {0}")]
+    Samples(#[from] crate::samples::Error),
+    #[error("vectors error: {0}")]
+    Vectors(#[from] vectors::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Go enums error: {0}")]
+    Enums(#[from] golang::enums::Error),
+    #[error("{0}")]
+    Usage(String),
+    #[error("Go short_vec error: {0}")]
+    ShortVec(#[from] golang::short_vec::Error),
+    #[error("Go JSON error: {0}")]
+    GoJson(#[from] golang::json::Error),
+    #[error("Go tests error: {0}")]
+    GoTests(#[from] golang::vectors::Error),
+    #[error("proto error: {0}")]
+    Proto(#[from] proto::Error),
+    #[error("JSON Schema error: {0}")]
+    JsonSchema(#[from] json_schema::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("yaml error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// What is known about an upstream version besides its types.
+pub struct Version {
+    pub commit: &'static str,
+    /// The container stored in the blockstore.
+    pub root: &'static str,
+    /// Fields declared with `deserialize_with = "default_on_eof"`, as `Container.field`.
+    pub default_on_eof: Vec<String>,
+    /// Fields declared with `with = "short_vec"`, as `Container.field`.
+    pub short_vec: Vec<String>,
+    pub sources: Vec<Source>,
+}
+
+/// Choices about the generated code, shared by every version.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub go_enums: Enums,
+    pub go_module: Module,
+}
+
+impl Options {
+    /// Parses `--go-enums=structs|integers`, `--go-module=<module path>` and
+    /// `--go-runtime=<module path>`, the last one importing the serde runtime
+    /// from there instead of vendoring it.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+        for arg in args {
+            match arg.split_once('=') {
+                Some(("--go-enums", style)) => options.go_enums = style.parse()?,
+                Some(("--go-module", path)) if !path.is_empty() => {
+                    options.go_module.path = path.to_string()
+                }
+                Some(("--go-runtime", path)) if !path.is_empty() => {
+                    options.go_module.runtime = Some(path.to_string())
+                }
+                _ => return Err(Error::Usage(format!("unknown argument {:?}", arg))),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// A generated file, relative to the repository root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub path: PathBuf,
+    pub contents: Vec<u8>,
+}
+
+impl Artifact {
+    fn new(path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> Self {
+        Self {
+            path: path.into(),
+            contents: contents.into(),
+        }
+    }
+}
+
+/// The directory of the generated Go module.
+pub const GO_MODULE_DIR: &str = "go";
+
+/// Everything generated for every version.
+pub fn all(options: &Options) -> Result<Vec<Artifact>> {
+    let mut artifacts = module(options)?;
+    artifacts.extend(b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4::artifacts(
+        options,
+    )?);
+    artifacts.extend(ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb::artifacts(
+        options,
+    )?);
+    Ok(artifacts)
+}
+
+/// The Go module files shared by every version: `go.mod` and the vendored
+/// serde runtime, and the decoder chain.
+pub fn module(options: &Options) -> Result<Vec<Artifact>> {
+    let dir = Path::new(GO_MODULE_DIR);
+    let mut artifacts = vec![Artifact::new(
+        dir.join("go.mod"),
+        options.go_module.go_mod(),
+    )];
+    for (path, contents) in options.go_module.runtime_files() {
+        artifacts.push(Artifact::new(dir.join(path), contents));
+    }
+
+    // The decoder chain over every version.
+    let versions = [legacy::b7b4::COMMIT, legacy::ce598::COMMIT];
+    let packages: Vec<String> = versions.iter().map(|commit| package_name(commit)).collect();
+    let mut chain = Vec::new();
+    golang::chain::output(&mut chain, &options.go_module.path, &versions, &packages)?;
+    artifacts.push(Artifact::new(dir.join("meta").join("meta.go"), chain));
+    Ok(artifacts)
+}
+
+/// The name of the Go package, and of the other generated files, of `commit`.
+pub fn package_name(commit: &str) -> String {
+    "parse_legacy_transaction_status_meta_".to_string() + commit
+}
+
+/// Generates everything from the traced `registry` of `version` and from
+/// `samples` of its root type.
+pub fn artifacts<T: Serialize + DeserializeOwned>(
+    version: &Version,
+    registry: Registry,
+    samples: &[(String, T)],
+    options: &Options,
+) -> Result<Vec<Artifact>> {
+    let commit = version.commit;
+    let mut fixtures = Fixtures::new(commit, version.root);
+    for (name, sample) in samples {
+        fixtures.push(name, sample)?;
+    }
+    let mut artifacts = vec![Artifact::new(Fixtures::path(commit), fixtures.contents()?)];
+
+    let name = package_name(commit);
+
+    // Golang definitions, in their own package of the Go module, with decoders
+    // that enforce allocation limits and the short_vec length encoding.
+    let package = Path::new(GO_MODULE_DIR).join(&name);
+    let runtime = options.go_module.runtime_path();
+    let mut source = Vec::new();
+    let config = serde_generate::CodeGeneratorConfig::new(name.to_string())
+        .with_encodings(vec![serde_generate::Encoding::Bincode]);
+    let generator = serde_generate::golang::CodeGenerator::new(&config)
+        .with_serde_module_path(runtime.to_string());
+    let source = match options.go_enums {
+        Enums::Structs => {
+            generator.output(&mut source, &registry)?;
+            String::from_utf8_lossy(&source).to_string()
+        }
+        Enums::Integers => {
+            let mut split = registry.clone();
+            let units = Units::split(&mut split)?;
+            generator.output(&mut source, &split)?;
+            units.apply(&String::from_utf8_lossy(&source))?
+        }
+    };
+    let source = golang::limits::apply(&source);
+    let source = golang::short_vec::apply(&source, &version.short_vec)?;
+    artifacts.push(Artifact::new(
+        package.join(name.to_string() + ".go"),
+        source,
+    ));
+    let mut limits = Vec::new();
+    golang::limits::output(&mut limits, &name, runtime, &Limits::default())?;
+    artifacts.push(Artifact::new(
+        package.join(name.to_string() + "_limits.go"),
+        limits,
+    ));
+
+    // Golang JSON marshalling, in the RPC rendering.
+    let mut json = Vec::new();
+    golang::json::output(&mut json, &name, &registry, version.root, options.go_enums)?;
+    artifacts.push(Artifact::new(
+        package.join(name.to_string() + "_json.go"),
+        json,
+    ));
+
+    // Golang tests decoding the golden vectors.
+    let mut tests = Vec::new();
+    golang::vectors::output(
+        &mut tests,
+        &name,
+        &registry,
+        &version.short_vec,
+        options.go_enums,
+        &fixtures,
+    )?;
+    artifacts.push(Artifact::new(
+        package.join(name.to_string() + "_test.go"),
+        tests,
+    ));
+
+    // Protocol Buffers definitions.
+    let mut proto = Vec::new();
+    proto::output(&mut proto, &name, &registry)?;
+    artifacts.push(Artifact::new(name.to_string() + ".proto", proto));
+
+    // JSON Schema definitions, for the plain JSON encoding and for the RPC rendering.
+    let schema = json_schema::for_registry(&name, &registry, version.root, &version.short_vec)?;
+    artifacts.push(Artifact::new(
+        name.to_string() + ".schema.json",
+        serde_json::to_string_pretty(&schema)?,
+    ));
+    let rpc_schema = json_schema::rpc(&(name.to_string() + "_rpc"), &registry)?;
+    artifacts.push(Artifact::new(
+        name.to_string() + ".rpc.schema.json",
+        serde_json::to_string_pretty(&rpc_schema)?,
+    ));
+
+    // The registry along with the upstream definitions it was traced from.
+    let schema = Schema {
+        commit: commit.to_string(),
+        root: version.root.to_string(),
+        default_on_eof: version.default_on_eof.clone(),
+        short_vec: version.short_vec.clone(),
+        sources: version.sources.clone(),
+        registry,
+    };
+    artifacts.push(Artifact::new(Schema::path(commit), schema.contents()?));
+    Ok(artifacts)
+}
+
+pub fn write(artifacts: &[Artifact]) -> std::io::Result<()> {
+    for artifact in artifacts {
+        if let Some(dir) = artifact.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&artifact.path, &artifact.contents)?;
+    }
+    Ok(())
+}
+
+/// Compares `artifacts` with the files checked in under `root`, returning a
+/// unified diff for each one that differs. The files are checked in exactly as
+/// generated, Go ones included: they are not run through gofmt, so that
+/// checking them needs no Go toolchain and no particular gofmt version. Only
+/// line endings are normalized, so that checkouts with `core.autocrlf` still
+/// compare equal.
+pub fn verify(root: &Path, artifacts: &[Artifact]) -> Vec<String> {
+    let mut diffs = Vec::new();
+    for artifact in artifacts {
+        let path = root.join(&artifact.path);
+        let expected = String::from_utf8_lossy(&artifact.contents).replace("\r\n", "\n");
+        let actual = match std::fs::read(&path) {
+            Ok(contents) => String::from_utf8_lossy(&contents).replace("\r\n", "\n"),
+            Err(_) => String::new(),
+        };
+        if actual != expected {
+            let name = artifact.path.display().to_string();
+            let diff = TextDiff::from_lines(&actual, &expected)
+                .unified_diff()
+                .header(&format!("a/{}", name), &format!("b/{}", name))
+                .to_string();
+            diffs.push(diff);
+        }
+    }
+    diffs
+}
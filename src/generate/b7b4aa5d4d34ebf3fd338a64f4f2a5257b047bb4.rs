@@ -0,0 +1,44 @@
+use super::{Artifact, Options, Version};
+use crate::legacy::b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4::*;
+use crate::samples::{self, Sampler};
+use crate::schema::Source;
+
+pub fn version() -> Version {
+    Version {
+        commit: COMMIT,
+        root: "TransactionStatusMeta",
+        default_on_eof: Vec::new(),
+        short_vec: Vec::new(),
+        sources: sources(),
+    }
+}
+
+pub fn artifacts(options: &Options) -> super::Result<Vec<Artifact>> {
+    let (registry, samples) = sampler()?.enumerate::<TransactionStatusMeta>()?;
+    super::artifacts(&version(), registry, &samples, options)
+}
+
+/// A sampler that has traced every type, ready to enumerate samples covering
+/// every variant of every enum.
+pub fn sampler() -> samples::Result<Sampler> {
+    let mut sampler = Sampler::new();
+    sampler.trace::<InstructionError>()?;
+    sampler.trace::<TransactionError>()?;
+    sampler.trace::<Result<()>>()?;
+    Ok(sampler)
+}
+
+// The upstream definitions cited in the legacy module.
+fn sources() -> Vec<Source> {
+    vec![
+        Source::new(
+            "TransactionStatusMeta",
+            "transaction-status/src/lib.rs",
+            22,
+            27,
+        ),
+        Source::new("Result", "sdk/src/transaction.rs", 68, 68),
+        Source::new("TransactionError", "sdk/src/transaction.rs", 18, 66),
+        Source::new("InstructionError", "sdk/src/instruction.rs", 10, 125),
+    ]
+}
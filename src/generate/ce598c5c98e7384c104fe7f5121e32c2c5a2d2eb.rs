@@ -0,0 +1,68 @@
+use super::{Artifact, Options, Version};
+use crate::legacy::ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb::*;
+use crate::samples::{self, Sampler};
+use crate::schema::Source;
+use serde_reflection::Format;
+
+pub fn version() -> Version {
+    Version {
+        commit: COMMIT,
+        root: "TransactionStatusMeta",
+        default_on_eof: vec!["TransactionStatusMeta.innerInstructions".to_string()],
+        short_vec: short_vec(),
+        sources: sources(),
+    }
+}
+
+pub fn artifacts(options: &Options) -> super::Result<Vec<Artifact>> {
+    let (registry, samples) = sampler()?.enumerate::<TransactionStatusMeta>()?;
+    super::artifacts(&version(), registry, &samples, options)
+}
+
+/// A sampler that has traced every type, ready to enumerate samples covering
+/// every variant of every enum.
+pub fn sampler() -> samples::Result<Sampler> {
+    let mut sampler = Sampler::new();
+    // short_vec cannot be traced from its type.
+    sampler.fix(&CompiledInstruction {
+        program_id_index: 1,
+        accounts: vec![1, 2, 3],
+        data: vec![1, 2, 3],
+    })?;
+    for field in short_vec() {
+        sampler.declare(&field, Format::Seq(Box::new(Format::U8)));
+    }
+    sampler.trace::<InstructionError>()?;
+    sampler.trace::<TransactionError>()?;
+    sampler.trace::<Result<()>>()?;
+    Ok(sampler)
+}
+
+fn short_vec() -> Vec<String> {
+    vec![
+        "CompiledInstruction.accounts".to_string(),
+        "CompiledInstruction.data".to_string(),
+    ]
+}
+
+// The upstream definitions cited in the legacy module.
+fn sources() -> Vec<Source> {
+    vec![
+        Source::new(
+            "TransactionStatusMeta",
+            "transaction-status/src/lib.rs",
+            140,
+            147,
+        ),
+        Source::new(
+            "InnerInstructions",
+            "transaction-status/src/lib.rs",
+            96,
+            101,
+        ),
+        Source::new("CompiledInstruction", "sdk/src/instruction.rs", 225, 234),
+        Source::new("Result", "sdk/src/transaction.rs", 95, 95),
+        Source::new("TransactionError", "sdk/src/transaction.rs", 22, 93),
+        Source::new("InstructionError", "sdk/src/instruction.rs", 11, 170),
+    ]
+}
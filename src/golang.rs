@@ -0,0 +1,52 @@
+pub mod chain;
+pub mod enums;
+pub mod json;
+pub mod limits;
+pub mod module;
+pub mod short_vec;
+pub mod vectors;
+
+use heck::CamelCase;
+use serde_reflection::Format;
+
+/// The Go name serde-generate gives to a field or variant.
+pub fn quote_name(name: &str) -> String {
+    name.to_camel_case()
+}
+
+/// The Go type serde-generate uses for `format`.
+pub fn quote_type(format: &Format) -> String {
+    match format {
+        Format::TypeName(name) => name.clone(),
+        Format::Unit => "struct {}".to_string(),
+        Format::Bool => "bool".to_string(),
+        Format::I8 => "int8".to_string(),
+        Format::I16 => "int16".to_string(),
+        Format::I32 => "int32".to_string(),
+        Format::I64 => "int64".to_string(),
+        Format::I128 => "serde.Int128".to_string(),
+        Format::U8 => "uint8".to_string(),
+        Format::U16 => "uint16".to_string(),
+        Format::U32 => "uint32".to_string(),
+        Format::U64 => "uint64".to_string(),
+        Format::U128 => "serde.Uint128".to_string(),
+        Format::F32 => "float32".to_string(),
+        Format::F64 => "float64".to_string(),
+        Format::Char => "rune".to_string(),
+        Format::Str => "string".to_string(),
+        Format::Bytes => "[]byte".to_string(),
+        Format::Option(format) => format!("*{}", quote_type(format)),
+        Format::Seq(format) => format!("[]{}", quote_type(format)),
+        Format::Map { key, value } => format!("map[{}]{}", quote_type(key), quote_type(value)),
+        Format::Tuple(formats) => {
+            let fields: Vec<String> = formats
+                .iter()
+                .enumerate()
+                .map(|(index, format)| format!("Field{} {}", index, quote_type(format)))
+                .collect();
+            format!("struct {{{}}}", fields.join("; "))
+        }
+        Format::TupleArray { content, size } => format!("[{}]{}", size, quote_type(content)),
+        Format::Variable(_) => unreachable!("registry contains unresolved formats"),
+    }
+}
@@ -0,0 +1,180 @@
+use std::io::{Result, Write};
+
+/// Writes the `meta` package, whose `Chain` decodes a meta in any encoding
+/// in the order the blockstore reads them. `versions` are the commits of the
+/// legacy layouts, oldest first, with `packages` their generated packages.
+pub fn output(
+    out: &mut dyn Write,
+    module: &str,
+    versions: &[&str],
+    packages: &[String],
+) -> Result<()> {
+    write!(
+        out,
+        r#"// Package meta decodes TransactionStatusMeta blobs in any known encoding.
+package meta
+
+import (
+	"fmt"
+	"strings"
+
+"#
+    )?;
+    for package in packages {
+        writeln!(out, "\t\"{}/{}\"", module, package)?;
+    }
+    write!(
+        out,
+        r#")
+
+// Encoding is how a meta was written.
+type Encoding string
+
+const (
+	Protobuf Encoding = "protobuf"
+	Bincode  Encoding = "bincode"
+)
+
+// Current is the version of metas in the layouts of solana-storage-proto.
+const Current = "current"
+
+// Decoded is a meta along with how it was written.
+type Decoded struct {{
+	Encoding Encoding
+	// Version is Current or the commit of a legacy layout.
+	Version string
+	// Meta is what a Chain function returned, or a pointer to the
+	// TransactionStatusMeta of the legacy layout.
+	Meta interface{{}}
+}}
+
+// Chain decodes metas as protobuf first, then as bincode, the same way as
+// the Rust decoder. The current layouts come from Solana's own bindings and
+// are left to the caller; nil functions are skipped. Like the Rust decoder,
+// Protobuf should fail on metas without balances, which are short blobs of
+// another encoding.
+//
+// The legacy layouts are prefixes of the current bincode one, which reads
+// them too. They only tell the version of what it decoded: a blob that is
+// exactly one of them was written with it. Without a Bincode function they
+// decode the blob themselves.
+type Chain struct {{
+	Protobuf func(data []byte) (interface{{}}, error)
+	Bincode  func(data []byte) (interface{{}}, error)
+}}
+
+func (c Chain) Decode(data []byte) (*Decoded, error) {{
+	if len(data) == 0 {{
+		return nil, fmt.Errorf("empty blob")
+	}}
+	var failures []string
+	if c.Protobuf != nil {{
+		meta, err := c.Protobuf(data)
+		if err == nil {{
+			return &Decoded{{Encoding: Protobuf, Version: Current, Meta: meta}}, nil
+		}}
+		failures = append(failures, fmt.Sprintf("protobuf: %v", err))
+	}}
+	legacy, legacyFailures := decodeLegacy(data)
+	if c.Bincode == nil {{
+		if legacy != nil {{
+			return legacy, nil
+		}}
+		failures = append(failures, legacyFailures...)
+		return nil, fmt.Errorf("no encoding matches: %s", strings.Join(failures, "; "))
+	}}
+	meta, err := c.Bincode(data)
+	if err != nil {{
+		failures = append(failures, fmt.Sprintf("bincode: %v", err))
+		return nil, fmt.Errorf("no encoding matches: %s", strings.Join(failures, "; "))
+	}}
+	version := Current
+	if legacy != nil {{
+		version = legacy.Version
+	}}
+	return &Decoded{{Encoding: Bincode, Version: version, Meta: meta}}, nil
+}}
+
+// decodeLegacy decodes data with the first legacy layout it is exactly, oldest
+// first, or returns why none matches.
+func decodeLegacy(data []byte) (*Decoded, []string) {{
+	var failures []string
+"#
+    )?;
+    for (version, package) in versions.iter().zip(packages) {
+        write!(
+            out,
+            r#"	if meta, err := {package}.BincodeDeserializeTransactionStatusMeta(data); err == nil {{
+		return &Decoded{{Encoding: Bincode, Version: "{version}", Meta: &meta}}, nil
+	}} else {{
+		failures = append(failures, fmt.Sprintf("{version}: %v", err))
+	}}
+"#
+        )?;
+    }
+    write!(
+        out,
+        r#"	return nil, failures
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain() -> String {
+        let mut out = Vec::new();
+        output(
+            &mut out,
+            "example.com/m",
+            &["old", "new"],
+            &["p_old".to_string(), "p_new".to_string()],
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn position(chain: &str, needle: &str) -> usize {
+        chain
+            .find(needle)
+            .unwrap_or_else(|| panic!("{:?} not emitted", needle))
+    }
+
+    #[test]
+    fn imports_the_packages() {
+        let chain = chain();
+        assert!(chain.contains("\t\"example.com/m/p_old\"\n\t\"example.com/m/p_new\"\n"));
+    }
+
+    #[test]
+    fn same_order_as_rust() {
+        // Protobuf, then the current bincode layout, which the legacy ones
+        // only tag with a version, oldest first.
+        let chain = chain();
+        let decode = position(&chain, "func (c Chain) Decode");
+        let protobuf = position(&chain, "c.Protobuf(data)");
+        let bincode = position(&chain, "c.Bincode(data)");
+        let version = position(&chain, "version = legacy.Version");
+        assert!(decode < protobuf && protobuf < bincode && bincode < version);
+
+        let legacy = position(&chain, "func decodeLegacy");
+        let old = position(
+            &chain,
+            "p_old.BincodeDeserializeTransactionStatusMeta(data)",
+        );
+        let new = position(
+            &chain,
+            "p_new.BincodeDeserializeTransactionStatusMeta(data)",
+        );
+        assert!(legacy < old && old < new);
+        assert!(chain.contains(r#"Version: "old", Meta: &meta"#));
+    }
+
+    #[test]
+    fn rejects_empty_blobs() {
+        let chain = chain();
+        assert!(position(&chain, "len(data) == 0") < position(&chain, "c.Protobuf(data)"));
+    }
+}
@@ -0,0 +1,462 @@
+use super::{limits, quote_name};
+use serde_reflection::{ContainerFormat, Registry, VariantFormat};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("unknown Go enum style {0:?}, expected structs or integers")]
+    UnknownStyle(String),
+    #[error("{0} would be defined twice")]
+    NameClash(String),
+    #[error("no Deserialize{0} function in the generated Go code")]
+    MissingDeserializer(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// How enums are emitted in Go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Enums {
+    /// serde-generate's: an interface implemented by one struct per variant.
+    #[default]
+    Structs,
+    /// Variants without a payload are constants of a typed integer, which is
+    /// the enum itself when no variant has a payload.
+    Integers,
+}
+
+impl FromStr for Enums {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "structs" => Ok(Self::Structs),
+            "integers" => Ok(Self::Integers),
+            _ => Err(Error::UnknownStyle(s.to_string())),
+        }
+    }
+}
+
+/// The Go constant of a variant without a payload, with `Enums::Integers`.
+pub fn constant(name: &str, variant: &str) -> String {
+    format!("{}{}", name, quote_name(variant))
+}
+
+/// The variants without a payload of the enums of a registry, taken out of it
+/// so that serde-generate only emits the others.
+pub struct Units {
+    enums: BTreeMap<String, Enum>,
+}
+
+struct Enum {
+    /// The Go type of the variants: `<Enum>Unit`, or the enum itself.
+    unit_type: String,
+    /// Whether the enum has no other variants, and is `unit_type` itself.
+    unit_only: bool,
+    variants: Vec<(u32, String)>,
+}
+
+impl Units {
+    /// Takes the variants without a payload out of the enums of `registry`,
+    /// removing the enums that have no other variant.
+    pub fn split(registry: &mut Registry) -> Result<Self> {
+        let mut enums = BTreeMap::new();
+        for (name, container) in registry.iter_mut() {
+            let variants = match container {
+                ContainerFormat::Enum(variants) => variants,
+                _ => continue,
+            };
+            let units: Vec<(u32, String)> = variants
+                .iter()
+                .filter(|(_, variant)| is_unit(variant))
+                .map(|(index, variant)| (*index, variant.name.clone()))
+                .collect();
+            if units.is_empty() {
+                continue;
+            }
+            variants.retain(|_, variant| !is_unit(variant));
+            let unit_only = variants.is_empty();
+            let unit_type = if unit_only {
+                name.clone()
+            } else {
+                format!("{}Unit", name)
+            };
+            enums.insert(
+                name.clone(),
+                Enum {
+                    unit_type,
+                    unit_only,
+                    variants: units,
+                },
+            );
+        }
+        registry.retain(|name, _| !enums.get(name).is_some_and(|e| e.unit_only));
+
+        // The new names must not clash with each other or with the containers.
+        let mut names: Vec<String> = registry.keys().cloned().collect();
+        for (name, e) in &enums {
+            if !e.unit_only {
+                names.push(e.unit_type.clone());
+            }
+            names.extend(
+                e.variants
+                    .iter()
+                    .map(|(_, variant)| constant(name, variant)),
+            );
+        }
+        names.sort();
+        if let Some(pair) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(Error::NameClash(pair[0].clone()));
+        }
+        Ok(Self { enums })
+    }
+
+    /// Completes the code serde-generate emitted for the split registry.
+    pub fn apply(&self, source: &str) -> Result<String> {
+        let mut source = source.to_string();
+        for (name, e) in &self.enums {
+            let indices: Vec<String> = e.variants.iter().map(|(i, _)| i.to_string()).collect();
+            if e.unit_only {
+                source.push_str(&unit_enum(name, &indices));
+            } else {
+                // Decode the variants without a payload before serde-generate's cases.
+                let function = format!("func Deserialize{}(", name);
+                let start = source
+                    .find(&function)
+                    .ok_or_else(|| Error::MissingDeserializer(name.clone()))?;
+                let switch = start
+                    + source[start..]
+                        .find("\tswitch index {\n")
+                        .ok_or_else(|| Error::MissingDeserializer(name.clone()))?;
+                let cases = format!(
+                    r#"	switch index {{
+	case {}:
+		if err := deserializer.IncreaseContainerDepth(); err != nil {{ return nil, err }}
+		deserializer.DecreaseContainerDepth()
+		return {}(index), nil
+
+"#,
+                    indices.join(", "),
+                    e.unit_type
+                );
+                source.replace_range(switch..switch + "\tswitch index {\n".len(), &cases);
+                source.push_str(&unit_variants(name, &e.unit_type));
+            }
+            source.push_str(&constants(name, e));
+        }
+        Ok(source)
+    }
+}
+
+fn is_unit(variant: &serde_reflection::Named<VariantFormat>) -> bool {
+    matches!(variant.value, VariantFormat::Unit)
+}
+
+fn constants(name: &str, e: &Enum) -> String {
+    let mut constants = String::from("\nconst (\n");
+    for (index, variant) in &e.variants {
+        constants.push_str(&format!(
+            "\t{} {} = {}\n",
+            constant(name, variant),
+            e.unit_type,
+            index
+        ));
+    }
+    constants.push_str(")\n");
+    constants
+}
+
+fn unit_variants(name: &str, unit_type: &str) -> String {
+    format!(
+        r#"
+// {unit_type} holds the variants of {name} without a payload.
+type {unit_type} uint32
+
+func ({unit_type}) is{name}() {{}}
+
+func (obj {unit_type}) Serialize(serializer serde.Serializer) error {{
+	if err := serializer.IncreaseContainerDepth(); err != nil {{ return err }}
+	serializer.SerializeVariantIndex(uint32(obj))
+	serializer.DecreaseContainerDepth()
+	return nil
+}}
+
+func (obj {unit_type}) BincodeSerialize() ([]byte, error) {{
+	serializer := bincode.NewSerializer();
+	if err := obj.Serialize(serializer); err != nil {{ return nil, err }}
+	return serializer.GetBytes(), nil
+}}
+"#
+    )
+}
+
+fn unit_enum(name: &str, indices: &[String]) -> String {
+    format!(
+        r#"
+type {name} uint32
+
+func (obj *{name}) Serialize(serializer serde.Serializer) error {{
+	if err := serializer.IncreaseContainerDepth(); err != nil {{ return err }}
+	serializer.SerializeVariantIndex(uint32(*obj))
+	serializer.DecreaseContainerDepth()
+	return nil
+}}
+
+func (obj *{name}) BincodeSerialize() ([]byte, error) {{
+	if obj == nil {{
+		return nil, fmt.Errorf("Cannot serialize null object")
+	}}
+	serializer := bincode.NewSerializer();
+	if err := obj.Serialize(serializer); err != nil {{ return nil, err }}
+	return serializer.GetBytes(), nil
+}}
+
+func Deserialize{name}(deserializer serde.Deserializer) ({name}, error) {{
+	index, err := deserializer.DeserializeVariantIndex()
+	if err != nil {{ return 0, err }}
+
+	switch index {{
+	case {indices}:
+		if err := deserializer.IncreaseContainerDepth(); err != nil {{ return 0, err }}
+		deserializer.DecreaseContainerDepth()
+		return {name}(index), nil
+
+	default:
+		return 0, fmt.Errorf("Unknown variant index for {name}: %d", index)
+	}}
+}}
+
+func BincodeDeserialize{name}(input []byte) ({name}, error) {{
+	if input == nil {{
+		var obj {name}
+		return obj, fmt.Errorf("Cannot deserialize null array")
+	}}
+{deserializer}	obj, err := Deserialize{name}(deserializer)
+	if err == nil && deserializer.GetBufferOffset() < uint64(len(input)) {{
+		return obj, fmt.Errorf("Some input bytes were not read")
+	}}
+	return obj, err
+}}
+"#,
+        indices = indices.join(", "),
+        deserializer = limits::deserializer(name),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use serde_reflection::{Samples, Tracer, TracerConfig};
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    enum Mixed {
+        A,
+        B(u8),
+        C,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    enum Flag {
+        On,
+        Off,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    struct Holder {
+        mixed: Mixed,
+        flag: Flag,
+    }
+
+    fn registry() -> Registry {
+        let mut tracer = Tracer::new(TracerConfig::default());
+        let samples = Samples::new();
+        tracer.trace_type::<Mixed>(&samples).unwrap();
+        tracer.trace_type::<Flag>(&samples).unwrap();
+        tracer.trace_type::<Holder>(&samples).unwrap();
+        tracer.registry().unwrap()
+    }
+
+    #[test]
+    fn split_takes_out_unit_variants() {
+        let mut registry = registry();
+        let units = Units::split(&mut registry).unwrap();
+        // Flag only had unit variants, Mixed keeps B.
+        assert_eq!(registry.keys().collect::<Vec<_>>(), vec!["Holder", "Mixed"]);
+        match &registry["Mixed"] {
+            ContainerFormat::Enum(variants) => {
+                let names: Vec<_> = variants.values().map(|v| v.name.as_str()).collect();
+                assert_eq!(names, vec!["B"]);
+            }
+            other => panic!("Mixed is {:?}", other),
+        }
+        assert_eq!(units.enums["Mixed"].unit_type, "MixedUnit");
+        assert_eq!(
+            units.enums["Mixed"].variants,
+            vec![(0, "A".to_string()), (2, "C".to_string())]
+        );
+        assert!(units.enums["Flag"].unit_only);
+        assert_eq!(units.enums["Flag"].unit_type, "Flag");
+    }
+
+    #[test]
+    fn names_must_not_clash() {
+        #[allow(dead_code)]
+        #[derive(Serialize, Deserialize)]
+        struct MixedA;
+        let mut tracer = Tracer::new(TracerConfig::default());
+        tracer.trace_type::<Mixed>(&Samples::new()).unwrap();
+        tracer.trace_type::<MixedA>(&Samples::new()).unwrap();
+        let mut registry = tracer.registry().unwrap();
+        assert!(matches!(
+            Units::split(&mut registry),
+            Err(Error::NameClash(name)) if name == "MixedA"
+        ));
+    }
+
+    /// What serde-generate emits for the split `Mixed`, cut down to the lines
+    /// that are patched.
+    const MIXED: &str = r#"func DeserializeMixed(deserializer serde.Deserializer) (Mixed, error) {
+	index, err := deserializer.DeserializeVariantIndex()
+	if err != nil { return nil, err }
+
+	switch index {
+	case 1:
+		if val, err := load_Mixed__B(deserializer); err == nil {
+			return &val, nil
+		} else {
+			return nil, err
+		}
+
+	default:
+		return nil, fmt.Errorf("Unknown variant index for Mixed: %d", index)
+	}
+}
+"#;
+
+    #[test]
+    fn mixed_enum_snapshot() {
+        let mut registry = registry();
+        registry.remove("Flag");
+        let units = Units::split(&mut registry).unwrap();
+        let expected = r#"func DeserializeMixed(deserializer serde.Deserializer) (Mixed, error) {
+	index, err := deserializer.DeserializeVariantIndex()
+	if err != nil { return nil, err }
+
+	switch index {
+	case 0, 2:
+		if err := deserializer.IncreaseContainerDepth(); err != nil { return nil, err }
+		deserializer.DecreaseContainerDepth()
+		return MixedUnit(index), nil
+
+	case 1:
+		if val, err := load_Mixed__B(deserializer); err == nil {
+			return &val, nil
+		} else {
+			return nil, err
+		}
+
+	default:
+		return nil, fmt.Errorf("Unknown variant index for Mixed: %d", index)
+	}
+}
+
+// MixedUnit holds the variants of Mixed without a payload.
+type MixedUnit uint32
+
+func (MixedUnit) isMixed() {}
+
+func (obj MixedUnit) Serialize(serializer serde.Serializer) error {
+	if err := serializer.IncreaseContainerDepth(); err != nil { return err }
+	serializer.SerializeVariantIndex(uint32(obj))
+	serializer.DecreaseContainerDepth()
+	return nil
+}
+
+func (obj MixedUnit) BincodeSerialize() ([]byte, error) {
+	serializer := bincode.NewSerializer();
+	if err := obj.Serialize(serializer); err != nil { return nil, err }
+	return serializer.GetBytes(), nil
+}
+
+const (
+	MixedA MixedUnit = 0
+	MixedC MixedUnit = 2
+)
+"#;
+        assert_eq!(units.apply(MIXED).unwrap(), expected);
+    }
+
+    #[test]
+    fn unit_only_enum_snapshot() {
+        let mut registry = registry();
+        let units = Units::split(&mut registry).unwrap();
+        let applied = units.apply(MIXED).unwrap();
+        let deserialize = r#"
+func DeserializeFlag(deserializer serde.Deserializer) (Flag, error) {
+	index, err := deserializer.DeserializeVariantIndex()
+	if err != nil { return 0, err }
+
+	switch index {
+	case 0, 1:
+		if err := deserializer.IncreaseContainerDepth(); err != nil { return 0, err }
+		deserializer.DecreaseContainerDepth()
+		return Flag(index), nil
+
+	default:
+		return 0, fmt.Errorf("Unknown variant index for Flag: %d", index)
+	}
+}
+"#;
+        assert!(applied.contains(deserialize), "{}", applied);
+        assert!(applied.contains("\nconst (\n\tFlagOn Flag = 0\n\tFlagOff Flag = 1\n)\n"));
+        // Decoded within the limits, like serde-generate's own functions.
+        assert!(applied.contains(&limits::deserializer("Flag")));
+    }
+
+    #[test]
+    fn generated_code_is_patched() {
+        let mut registry = registry();
+        let units = Units::split(&mut registry).unwrap();
+        let config = serde_generate::CodeGeneratorConfig::new("p".to_string())
+            .with_encodings(vec![serde_generate::Encoding::Bincode]);
+        let mut source = Vec::new();
+        serde_generate::golang::CodeGenerator::new(&config)
+            .output(&mut source, &registry)
+            .unwrap();
+        let applied = units.apply(&String::from_utf8(source).unwrap()).unwrap();
+        assert!(applied.contains("\tcase 0, 2:\n"));
+        assert!(applied.contains("\t\treturn MixedUnit(index), nil\n"));
+        // serde-generate only knows of B.
+        assert!(!applied.contains("Mixed__A"));
+    }
+
+    #[test]
+    fn missing_deserializer() {
+        let mut registry = registry();
+        let units = Units::split(&mut registry).unwrap();
+        assert!(matches!(
+            units.apply(""),
+            Err(Error::MissingDeserializer(name)) if name == "Mixed"
+        ));
+    }
+
+    #[test]
+    fn styles() {
+        assert_eq!("structs".parse::<Enums>().unwrap(), Enums::Structs);
+        assert_eq!("integers".parse::<Enums>().unwrap(), Enums::Integers);
+        assert!(matches!(
+            "ints".parse::<Enums>(),
+            Err(Error::UnknownStyle(_))
+        ));
+        assert_eq!(
+            constant("InstructionError", "GenericError"),
+            "InstructionErrorGenericError"
+        );
+    }
+}
@@ -0,0 +1,1021 @@
+use super::enums::{self, Enums};
+use super::{quote_name, quote_type};
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
+use std::collections::BTreeMap;
+use std::io::Write;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("no JSON encoding for {0:?}")]
+    Unsupported(Format),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Writes a Go file giving the types generated for `registry` `MarshalJSON`
+/// and `UnmarshalJSON` methods, and each enum an `UnmarshalJSON<Enum>`
+/// function, in the RPC rendering described by `json_schema::rpc` and
+/// produced by `rpc::render`.
+pub fn output(
+    out: &mut dyn Write,
+    package: &str,
+    registry: &Registry,
+    root: &str,
+    enums: Enums,
+) -> Result<()> {
+    let mut formats = BTreeMap::new();
+    for container in registry.values() {
+        collect_container(container, &mut formats)?;
+    }
+
+    write!(out, "{}", HEADER.replace("{package}", package))?;
+    for (name, container) in registry {
+        let emitter = Emitter {
+            registry,
+            name,
+            enums,
+        };
+        let code = match container {
+            ContainerFormat::Enum(variants) => emitter.enumeration(variants),
+            _ => emitter.container(container, name == root),
+        };
+        write!(out, "{}", code)?;
+    }
+    for (mangled, format) in &formats {
+        write!(out, "{}", helpers(registry, enums, mangled, format))?;
+    }
+    Ok(())
+}
+
+/// The name serde-generate's helpers use for `format`, e.g. `option_vector_u8`.
+fn mangle(format: &Format) -> String {
+    match format {
+        Format::TypeName(name) => name.clone(),
+        Format::Unit => "unit".to_string(),
+        Format::Bool => "bool".to_string(),
+        Format::I8 => "i8".to_string(),
+        Format::I16 => "i16".to_string(),
+        Format::I32 => "i32".to_string(),
+        Format::I64 => "i64".to_string(),
+        Format::I128 => "i128".to_string(),
+        Format::U8 => "u8".to_string(),
+        Format::U16 => "u16".to_string(),
+        Format::U32 => "u32".to_string(),
+        Format::U64 => "u64".to_string(),
+        Format::U128 => "u128".to_string(),
+        Format::F32 => "f32".to_string(),
+        Format::F64 => "f64".to_string(),
+        Format::Char => "char".to_string(),
+        Format::Str => "str".to_string(),
+        Format::Bytes => "bytes".to_string(),
+        Format::Option(format) => format!("option_{}", mangle(format)),
+        Format::Seq(format) => format!("vector_{}", mangle(format)),
+        Format::Map { key, value } => format!("map_{}_to_{}", mangle(key), mangle(value)),
+        Format::Tuple(formats) => format!(
+            "tuple{}_{}",
+            formats.len(),
+            formats.iter().map(mangle).collect::<Vec<_>>().join("_")
+        ),
+        Format::TupleArray { content, size } => format!("array{}_{}_array", size, mangle(content)),
+        Format::Variable(_) => unreachable!("registry contains unresolved formats"),
+    }
+}
+
+fn collect(format: &Format, formats: &mut BTreeMap<String, Format>) -> Result<()> {
+    match format {
+        Format::I128 | Format::U128 | Format::Map { .. } => {
+            return Err(Error::Unsupported(format.clone()))
+        }
+        Format::Option(inner) | Format::Seq(inner) | Format::TupleArray { content: inner, .. } => {
+            collect(inner, formats)?
+        }
+        Format::Tuple(inner) => {
+            for format in inner {
+                collect(format, formats)?;
+            }
+        }
+        Format::Bytes => collect(&Format::Seq(Box::new(Format::U8)), formats)?,
+        Format::Variable(_) => unreachable!("registry contains unresolved formats"),
+        _ => (),
+    }
+    formats.insert(mangle(format), format.clone());
+    Ok(())
+}
+
+fn collect_container(
+    container: &ContainerFormat,
+    formats: &mut BTreeMap<String, Format>,
+) -> Result<()> {
+    let mut payloads = Vec::new();
+    match container {
+        ContainerFormat::UnitStruct => (),
+        ContainerFormat::NewTypeStruct(format) => payloads.push(format.as_ref()),
+        ContainerFormat::TupleStruct(inner) => payloads.extend(inner),
+        ContainerFormat::Struct(fields) => payloads.extend(fields.iter().map(|f| &f.value)),
+        ContainerFormat::Enum(variants) => {
+            for variant in variants.values() {
+                match &variant.value {
+                    VariantFormat::Unit => (),
+                    VariantFormat::NewType(format) => payloads.push(format.as_ref()),
+                    VariantFormat::Tuple(inner) => payloads.extend(inner),
+                    VariantFormat::Struct(fields) => {
+                        payloads.extend(fields.iter().map(|f| &f.value))
+                    }
+                    VariantFormat::Variable(_) => {
+                        unreachable!("registry contains unresolved variants")
+                    }
+                }
+            }
+        }
+    }
+    for format in payloads {
+        collect(format, formats)?;
+    }
+    Ok(())
+}
+
+/// What a container or variant holds, and how it is read from a Go `obj`.
+enum Payload<'a> {
+    Unit,
+    /// A newtype: `obj.Value` if serde-generate wrapped it in a struct, `obj` otherwise.
+    Value {
+        format: &'a Format,
+        wrapped: bool,
+    },
+    Tuple(&'a [Format]),
+    Struct(&'a [Named<Format>]),
+}
+
+impl<'a> Payload<'a> {
+    fn newtype(format: &'a Format) -> Self {
+        // See `golang::vectors::newtype_literal`.
+        let wrapped = matches!(format, Format::TypeName(_) | Format::Option(_));
+        Payload::Value { format, wrapped }
+    }
+}
+
+struct Emitter<'a> {
+    registry: &'a Registry,
+    /// The container being emitted.
+    name: &'a str,
+    enums: Enums,
+}
+
+impl<'a> Emitter<'a> {
+    fn container(&self, container: &ContainerFormat, is_root: bool) -> String {
+        let payload = match container {
+            ContainerFormat::UnitStruct => Payload::Unit,
+            ContainerFormat::NewTypeStruct(format) => Payload::newtype(format),
+            ContainerFormat::TupleStruct(formats) => Payload::Tuple(formats),
+            ContainerFormat::Struct(fields) => Payload::Struct(fields),
+            ContainerFormat::Enum(_) => unreachable!("enums are emitted by `enumeration`"),
+        };
+        let name = self.name;
+        let mut code = format!(
+            "\nfunc (obj {}) MarshalJSON() ([]byte, error) {{\n\tvar content json.RawMessage\n",
+            name
+        );
+        code += &self.encode(&payload, name);
+        if is_root && matches!(payload, Payload::Struct(_)) {
+            code += &self.rpc_err();
+        }
+        code += "\treturn content, nil\n}\n";
+        code += &format!(
+            "\nfunc (target *{0}) UnmarshalJSON(content []byte) error {{\n\tvar obj {0}\n",
+            name
+        );
+        code += &self.decode(&payload, name, name, "return ");
+        code += "\t*target = obj\n\treturn nil\n}\n";
+        code
+    }
+
+    /// The RPC adds an `err` field, first, mirroring the error of `status`.
+    fn rpc_err(&self) -> String {
+        let err_variant = match self.registry.get(self.name) {
+            Some(ContainerFormat::Struct(fields)) => fields
+                .iter()
+                .find(|field| field.name == "status")
+                .and_then(|field| match &field.value {
+                    Format::TypeName(result) => match self.registry.get(result) {
+                        Some(ContainerFormat::Enum(variants)) => variants
+                            .values()
+                            .find(|variant| variant.name == "Err")
+                            .filter(|variant| matches!(variant.value, VariantFormat::NewType(_)))
+                            .map(|variant| (result.clone(), variant)),
+                        _ => None,
+                    },
+                    _ => None,
+                }),
+            _ => None,
+        };
+        let mut code = "\terrValue := json.RawMessage(\"null\")\n".to_string();
+        if let Some((result, variant)) = err_variant {
+            if let VariantFormat::NewType(inner) = &variant.value {
+                let value = match Payload::newtype(inner) {
+                    Payload::Value { wrapped: true, .. } => "status.Value".to_string(),
+                    _ => format!("({})(*status)", quote_type(inner)),
+                };
+                code += &format!(
+                    "\tif status, ok := obj.Status.(*{}__Err); ok {{\n\t\tif errValue, err = json_encode_{}({}); err != nil {{ return nil, err }}\n\t}}\n",
+                    result,
+                    mangle(inner),
+                    value
+                );
+            }
+        }
+        code += "\tcontent = jsonPrepend(\"err\", errValue, content)\n";
+        code
+    }
+
+    fn enumeration(&self, variants: &BTreeMap<u32, Named<VariantFormat>>) -> String {
+        let name = self.name;
+        let unit_only = variants
+            .values()
+            .all(|variant| matches!(variant.value, VariantFormat::Unit));
+        let mut code = String::new();
+        let mut cases = String::new();
+        let fail = if self.enums == Enums::Integers && unit_only {
+            "return 0, "
+        } else {
+            "return nil, "
+        };
+
+        // Variants without a payload.
+        let units: Vec<&Named<VariantFormat>> = variants
+            .values()
+            .filter(|variant| matches!(variant.value, VariantFormat::Unit))
+            .collect();
+        if self.enums == Enums::Integers && !units.is_empty() {
+            let unit_type = if unit_only {
+                name.to_string()
+            } else {
+                format!("{}Unit", name)
+            };
+            code += &format!(
+                "\nfunc (obj {}) MarshalJSON() ([]byte, error) {{\n\tswitch obj {{\n",
+                unit_type
+            );
+            for variant in &units {
+                code += &format!(
+                    "\tcase {}:\n\t\treturn json.Marshal(\"{}\")\n",
+                    enums::constant(name, &variant.name),
+                    variant.name
+                );
+                cases += &format!(
+                    "\tcase \"{}\":\n\t\treturn {}, nil\n",
+                    variant.name,
+                    enums::constant(name, &variant.name)
+                );
+            }
+            code += &format!(
+                "\tdefault:\n\t\treturn nil, fmt.Errorf(\"unknown variant %d of {}\", uint32(obj))\n\t}}\n}}\n",
+                name
+            );
+        } else {
+            for variant in &units {
+                let full_name = format!("{}__{}", name, quote_name(&variant.name));
+                code += &format!(
+                    "\nfunc (obj {}) MarshalJSON() ([]byte, error) {{\n\treturn json.Marshal(\"{}\")\n}}\n",
+                    full_name, variant.name
+                );
+                cases += &format!(
+                    "\tcase \"{}\":\n\t\treturn &{}{{}}, nil\n",
+                    variant.name, full_name
+                );
+            }
+        }
+
+        // Variants with a payload.
+        for variant in variants.values() {
+            let payload = match &variant.value {
+                VariantFormat::Unit => continue,
+                VariantFormat::NewType(format) => Payload::newtype(format),
+                VariantFormat::Tuple(formats) => Payload::Tuple(formats),
+                VariantFormat::Struct(fields) => Payload::Struct(fields),
+                VariantFormat::Variable(_) => {
+                    unreachable!("registry contains unresolved variants")
+                }
+            };
+            let full_name = format!("{}__{}", name, quote_name(&variant.name));
+            let context = format!("{}::{}", name, variant.name);
+            code += &format!(
+                "\nfunc (obj {}) MarshalJSON() ([]byte, error) {{\n\tvar content json.RawMessage\n",
+                full_name
+            );
+            code += &self.encode(&payload, &context);
+            code += &format!(
+                "\treturn jsonObject([]string{{\"{}\"}}, []json.RawMessage{{content}}), nil\n}}\n",
+                variant.name
+            );
+            cases += &format!("\tcase \"{}\":\n\t\tvar obj {}\n", variant.name, full_name);
+            cases += &self
+                .decode(&payload, &full_name, &context, fail)
+                .lines()
+                .map(|line| format!("\t{}\n", line))
+                .collect::<String>();
+            cases += "\t\treturn &obj, nil\n";
+        }
+
+        if self.enums == Enums::Integers && unit_only {
+            code += &format!(
+                r#"
+func (target *{0}) UnmarshalJSON(data []byte) error {{
+	obj, err := UnmarshalJSON{0}(data)
+	if err != nil {{ return err }}
+	*target = obj
+	return nil
+}}
+"#,
+                name
+            );
+        }
+        code += &format!(
+            r#"
+// UnmarshalJSON{0} reads a {0} as written by its MarshalJSON methods.
+func UnmarshalJSON{0}(data []byte) ({0}, error) {{
+	name, content, err := jsonVariant(data)
+	if err != nil {{ {1}fmt.Errorf("{0}: %w", err) }}
+	switch name {{
+{2}	default:
+		{1}fmt.Errorf("unknown variant %q of {0}", name)
+	}}
+}}
+"#,
+            name, fail, cases
+        );
+        code
+    }
+
+    /// Statements setting `content` to the JSON of the payload of `obj`.
+    fn encode(&self, payload: &Payload, context: &str) -> String {
+        let fail = "err != nil { return nil, err }";
+        match payload {
+            Payload::Unit => "\tcontent = json.RawMessage(\"null\")\n".to_string(),
+            Payload::Value { format, wrapped } => {
+                let value = if *wrapped {
+                    "obj.Value".to_string()
+                } else {
+                    format!("({})(obj)", quote_type(format))
+                };
+                format!(
+                    "\tvar err error\n\tif content, err = json_encode_{}({}); {}\n",
+                    mangle(format),
+                    value,
+                    fail
+                )
+            }
+            Payload::Tuple(formats) => {
+                let mut code = format!(
+                    "\tvar err error\n\titems := make([]json.RawMessage, {})\n",
+                    formats.len()
+                );
+                for (index, format) in formats.iter().enumerate() {
+                    code += &format!(
+                        "\tif items[{0}], err = json_encode_{1}(obj.Field{0}); {2}\n",
+                        index,
+                        mangle(format),
+                        fail
+                    );
+                }
+                code += &format!("\tif content, err = json.Marshal(items); {}\n", fail);
+                code
+            }
+            Payload::Struct(fields) => {
+                let mut code = format!(
+                    "\tvar err error\n\tvalues := make([]json.RawMessage, {})\n",
+                    fields.len()
+                );
+                for (index, field) in fields.iter().enumerate() {
+                    code += &format!(
+                        "\tif values[{}], err = {}(obj.{}); {}\n",
+                        index,
+                        self.field_encoder(context, field),
+                        quote_name(&field.name),
+                        fail
+                    );
+                }
+                let keys: Vec<String> = fields
+                    .iter()
+                    .map(|field| format!("\"{}\"", field.name))
+                    .collect();
+                code += &format!(
+                    "\tcontent = jsonObject([]string{{{}}}, values)\n",
+                    keys.join(", ")
+                );
+                code
+            }
+        }
+    }
+
+    /// Statements setting the payload of `obj`, of type `go_name`, from the
+    /// JSON in `content`, returning with `fail` followed by the error otherwise.
+    fn decode(&self, payload: &Payload, go_name: &str, context: &str, fail: &str) -> String {
+        let fail_with =
+            |message: &str| format!("{{ {}fmt.Errorf(\"{}: %w\", err) }}", fail, message);
+        match payload {
+            Payload::Unit => String::new(),
+            Payload::Value { format, wrapped } => {
+                let mut code = format!(
+                    "\tval, err := json_decode_{}(content)\n\tif err != nil {}\n",
+                    mangle(format),
+                    fail_with(context)
+                );
+                code += &if *wrapped {
+                    "\tobj.Value = val\n".to_string()
+                } else {
+                    format!("\tobj = ({})(val)\n", go_name)
+                };
+                code
+            }
+            Payload::Tuple(formats) => {
+                let mut code = format!(
+                    "\titems, err := jsonItems(content, {})\n\tif err != nil {}\n",
+                    formats.len(),
+                    fail_with(context)
+                );
+                for (index, format) in formats.iter().enumerate() {
+                    code += &format!(
+                        "\tif obj.Field{0}, err = json_decode_{1}(items[{0}]); err != nil {2}\n",
+                        index,
+                        mangle(format),
+                        fail_with(&format!("{}.{}", context, index))
+                    );
+                }
+                code
+            }
+            Payload::Struct([]) => format!(
+                "\tif _, err := jsonFields(content); err != nil {}\n",
+                fail_with(context)
+            ),
+            Payload::Struct(fields) => {
+                let mut code = format!(
+                    "\tfields, err := jsonFields(content)\n\tif err != nil {}\n",
+                    fail_with(context)
+                );
+                for field in fields.iter() {
+                    code += &format!(
+                        "\tif obj.{}, err = {}(fields[\"{}\"]); err != nil {}\n",
+                        quote_name(&field.name),
+                        self.field_decoder(context, field),
+                        field.name,
+                        fail_with(&format!("{}.{}", context, field.name))
+                    );
+                }
+                code
+            }
+        }
+    }
+
+    /// The RPC renders the data of compiled instructions in base58.
+    fn is_base58(&self, context: &str, field: &Named<Format>) -> bool {
+        context == "CompiledInstruction"
+            && field.name == "data"
+            && field.value == Format::Seq(Box::new(Format::U8))
+    }
+
+    fn field_encoder(&self, context: &str, field: &Named<Format>) -> String {
+        if self.is_base58(context, field) {
+            "json_encode_base58".to_string()
+        } else {
+            format!("json_encode_{}", mangle(&field.value))
+        }
+    }
+
+    fn field_decoder(&self, context: &str, field: &Named<Format>) -> String {
+        if self.is_base58(context, field) {
+            "json_decode_base58".to_string()
+        } else {
+            format!("json_decode_{}", mangle(&field.value))
+        }
+    }
+}
+
+/// `json_encode_<format>` and `json_decode_<format>` functions.
+fn helpers(registry: &Registry, enums: Enums, mangled: &str, format: &Format) -> String {
+    let go_type = quote_type(format);
+    let (encode, decode) = match format {
+        Format::TypeName(name) => {
+            let is_interface = match registry.get(name) {
+                Some(ContainerFormat::Enum(variants)) => {
+                    enums == Enums::Structs
+                        || !variants
+                            .values()
+                            .all(|variant| matches!(variant.value, VariantFormat::Unit))
+                }
+                _ => false,
+            };
+            if is_interface {
+                (
+                    "\treturn json.Marshal(value)\n".to_string(),
+                    format!("\treturn UnmarshalJSON{}(data)\n", name),
+                )
+            } else {
+                (
+                    "\treturn value.MarshalJSON()\n".to_string(),
+                    format!(
+                        "\tvar obj {}\n\terr := obj.UnmarshalJSON(data)\n\treturn obj, err\n",
+                        name
+                    ),
+                )
+            }
+        }
+        Format::Unit => (
+            "\treturn json.RawMessage(\"null\"), nil\n".to_string(),
+            "\tif !isJSONNull(data) { return struct {}{}, fmt.Errorf(\"expected null\") }\n\treturn struct {}{}, nil\n"
+                .to_string(),
+        ),
+        Format::Char => (
+            "\treturn json.Marshal(string(value))\n".to_string(),
+            r#"	var obj string
+	if err := json.Unmarshal(data, &obj); err != nil { return 0, err }
+	runes := []rune(obj)
+	if len(runes) != 1 { return 0, fmt.Errorf("expected a single character") }
+	return runes[0], nil
+"#
+            .to_string(),
+        ),
+        Format::Option(inner) => (
+            format!(
+                "\tif value == nil {{ return json.RawMessage(\"null\"), nil }}\n\treturn json_encode_{}(*value)\n",
+                mangle(inner)
+            ),
+            format!(
+                "\tif isJSONNull(data) {{ return nil, nil }}\n\tobj, err := json_decode_{}(data)\n\tif err != nil {{ return nil, err }}\n\treturn &obj, nil\n",
+                mangle(inner)
+            ),
+        ),
+        Format::Seq(inner) | Format::TupleArray { content: inner, .. } => {
+            let make = match format {
+                Format::TupleArray { size, .. } => format!(
+                    "\titems, err := jsonItems(data, {})\n\tif err != nil {{ return obj, err }}\n",
+                    size
+                ),
+                _ => "\tvar items []json.RawMessage\n\tif err := json.Unmarshal(data, &items); err != nil { return obj, err }\n\tobj = make([]".to_string()
+                    + &quote_type(inner)
+                    + ", len(items))\n",
+            };
+            (
+                format!(
+                    r#"	items := make([]json.RawMessage, len(value))
+	for i, item := range value {{
+		var err error
+		if items[i], err = json_encode_{0}(item); err != nil {{ return nil, err }}
+	}}
+	return json.Marshal(items)
+"#,
+                    mangle(inner)
+                ),
+                format!(
+                    r#"	var obj {1}
+{2}	for i, item := range items {{
+		var err error
+		if obj[i], err = json_decode_{0}(item); err != nil {{ return obj, err }}
+	}}
+	return obj, nil
+"#,
+                    mangle(inner),
+                    go_type,
+                    make
+                ),
+            )
+        }
+        Format::Bytes => (
+            "\treturn json_encode_vector_u8(value)\n".to_string(),
+            "\treturn json_decode_vector_u8(data)\n".to_string(),
+        ),
+        Format::Tuple(formats) => {
+            let mut encode = format!(
+                "\titems := make([]json.RawMessage, {})\n\tvar err error\n",
+                formats.len()
+            );
+            let mut decode = format!(
+                "\tvar obj {}\n\titems, err := jsonItems(data, {})\n\tif err != nil {{ return obj, err }}\n",
+                go_type,
+                formats.len()
+            );
+            for (index, format) in formats.iter().enumerate() {
+                encode += &format!(
+                    "\tif items[{0}], err = json_encode_{1}(value.Field{0}); err != nil {{ return nil, err }}\n",
+                    index,
+                    mangle(format)
+                );
+                decode += &format!(
+                    "\tif obj.Field{0}, err = json_decode_{1}(items[{0}]); err != nil {{ return obj, err }}\n",
+                    index,
+                    mangle(format)
+                );
+            }
+            encode += "\treturn json.Marshal(items)\n";
+            decode += "\treturn obj, nil\n";
+            (encode, decode)
+        }
+        // Numbers, booleans and strings are the same in both languages.
+        _ => (
+            "\treturn json.Marshal(value)\n".to_string(),
+            format!(
+                "\tvar obj {}\n\terr := json.Unmarshal(data, &obj)\n\treturn obj, err\n",
+                go_type
+            ),
+        ),
+    };
+    format!(
+        "\nfunc json_encode_{0}(value {1}) (json.RawMessage, error) {{\n{2}}}\n\nfunc json_decode_{0}(data json.RawMessage) ({1}, error) {{\n{3}}}\n",
+        mangled, go_type, encode, decode
+    )
+}
+
+const HEADER: &str = r#"package {package}
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"strings"
+)
+
+// jsonObject writes the fields in order, as serde_json does.
+func jsonObject(keys []string, values []json.RawMessage) json.RawMessage {
+	var buffer bytes.Buffer
+	buffer.WriteByte('{')
+	for i, key := range keys {
+		if i > 0 {
+			buffer.WriteByte(',')
+		}
+		name, _ := json.Marshal(key)
+		buffer.Write(name)
+		buffer.WriteByte(':')
+		buffer.Write(values[i])
+	}
+	buffer.WriteByte('}')
+	return buffer.Bytes()
+}
+
+// jsonPrepend adds a field in front of those of an object written by jsonObject.
+func jsonPrepend(key string, value json.RawMessage, object json.RawMessage) json.RawMessage {
+	first := jsonObject([]string{key}, []json.RawMessage{value})
+	if len(object) == 2 {
+		return first
+	}
+	return append(append(first[:len(first)-1], ','), object[1:]...)
+}
+
+func jsonFields(data []byte) (map[string]json.RawMessage, error) {
+	var fields map[string]json.RawMessage
+	if err := json.Unmarshal(data, &fields); err != nil { return nil, err }
+	if fields == nil { return nil, fmt.Errorf("expected an object") }
+	return fields, nil
+}
+
+func jsonItems(data []byte, length int) ([]json.RawMessage, error) {
+	var items []json.RawMessage
+	if err := json.Unmarshal(data, &items); err != nil { return nil, err }
+	if len(items) != length { return nil, fmt.Errorf("expected %d items, got %d", length, len(items)) }
+	return items, nil
+}
+
+// jsonVariant splits an enum value, either the name of a variant without a
+// payload or an object with the variant name as its only key.
+func jsonVariant(data []byte) (string, json.RawMessage, error) {
+	var name string
+	if err := json.Unmarshal(data, &name); err == nil {
+		return name, nil, nil
+	}
+	fields, err := jsonFields(data)
+	if err != nil { return "", nil, err }
+	if len(fields) != 1 { return "", nil, fmt.Errorf("expected a single variant, got %d", len(fields)) }
+	for name, content := range fields {
+		return name, content, nil
+	}
+	panic("unreachable")
+}
+
+// isJSONNull also holds for missing fields, which serde reads as None.
+func isJSONNull(data []byte) bool {
+	trimmed := bytes.TrimSpace(data)
+	return len(trimmed) == 0 || bytes.Equal(trimmed, []byte("null"))
+}
+
+const base58Alphabet = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+func json_encode_base58(value []uint8) (json.RawMessage, error) {
+	zeros := 0
+	for zeros < len(value) && value[zeros] == 0 {
+		zeros++
+	}
+	// Base 58 digits, least significant first.
+	var digits []byte
+	for _, b := range value[zeros:] {
+		carry := int(b)
+		for i := range digits {
+			carry += int(digits[i]) << 8
+			digits[i] = byte(carry % 58)
+			carry /= 58
+		}
+		for carry > 0 {
+			digits = append(digits, byte(carry%58))
+			carry /= 58
+		}
+	}
+	var encoded strings.Builder
+	encoded.WriteString(strings.Repeat("1", zeros))
+	for i := len(digits) - 1; i >= 0; i-- {
+		encoded.WriteByte(base58Alphabet[digits[i]])
+	}
+	return json.Marshal(encoded.String())
+}
+
+func json_decode_base58(data json.RawMessage) ([]uint8, error) {
+	var encoded string
+	if err := json.Unmarshal(data, &encoded); err != nil { return nil, err }
+	zeros := 0
+	for zeros < len(encoded) && encoded[zeros] == '1' {
+		zeros++
+	}
+	// Bytes, least significant first.
+	var decoded []byte
+	for _, c := range encoded[zeros:] {
+		carry := strings.IndexRune(base58Alphabet, c)
+		if carry < 0 { return nil, fmt.Errorf("invalid base58 character %q", c) }
+		for i := range decoded {
+			carry += int(decoded[i]) * 58
+			decoded[i] = byte(carry & 0xff)
+			carry >>= 8
+		}
+		for carry > 0 {
+			decoded = append(decoded, byte(carry&0xff))
+			carry >>= 8
+		}
+	}
+	obj := make([]uint8, zeros+len(decoded))
+	for i, b := range decoded {
+		obj[len(obj)-1-i] = b
+	}
+	return obj, nil
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use serde_reflection::{Samples, Tracer, TracerConfig};
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    enum InstructionError {
+        GenericError,
+        Custom(u32),
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    enum TransactionError {
+        AccountInUse,
+        InstructionError(u8, InstructionError),
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    struct CompiledInstruction {
+        program_id_index: u8,
+        data: Vec<u8>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    struct Meta {
+        status: std::result::Result<(), TransactionError>,
+        fee: u64,
+        instructions: Vec<CompiledInstruction>,
+    }
+
+    fn registry() -> Registry {
+        let mut tracer = Tracer::new(TracerConfig::default());
+        let samples = Samples::new();
+        tracer.trace_type::<InstructionError>(&samples).unwrap();
+        tracer.trace_type::<TransactionError>(&samples).unwrap();
+        tracer
+            .trace_type::<std::result::Result<(), TransactionError>>(&samples)
+            .unwrap();
+        tracer.trace_type::<Meta>(&samples).unwrap();
+        tracer.registry().unwrap()
+    }
+
+    fn generate(enums: Enums) -> String {
+        let mut out = Vec::new();
+        output(&mut out, "p", &registry(), "Meta", enums).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// The encoders writing `{"InstructionError":[0,{"Custom":1}]}`.
+    #[test]
+    fn instruction_error_snapshot() {
+        let code = generate(Enums::Structs);
+        let tuple = r#"
+func (obj TransactionError__InstructionError) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	items := make([]json.RawMessage, 2)
+	if items[0], err = json_encode_u8(obj.Field0); err != nil { return nil, err }
+	if items[1], err = json_encode_InstructionError(obj.Field1); err != nil { return nil, err }
+	if content, err = json.Marshal(items); err != nil { return nil, err }
+	return jsonObject([]string{"InstructionError"}, []json.RawMessage{content}), nil
+}
+
+// UnmarshalJSONTransactionError reads a TransactionError as written by its MarshalJSON methods.
+func UnmarshalJSONTransactionError(data []byte) (TransactionError, error) {
+	name, content, err := jsonVariant(data)
+	if err != nil { return nil, fmt.Errorf("TransactionError: %w", err) }
+	switch name {
+	case "AccountInUse":
+		return &TransactionError__AccountInUse{}, nil
+	case "InstructionError":
+		var obj TransactionError__InstructionError
+		items, err := jsonItems(content, 2)
+		if err != nil { return nil, fmt.Errorf("TransactionError::InstructionError: %w", err) }
+		if obj.Field0, err = json_decode_u8(items[0]); err != nil { return nil, fmt.Errorf("TransactionError::InstructionError.0: %w", err) }
+		if obj.Field1, err = json_decode_InstructionError(items[1]); err != nil { return nil, fmt.Errorf("TransactionError::InstructionError.1: %w", err) }
+		return &obj, nil
+	default:
+		return nil, fmt.Errorf("unknown variant %q of TransactionError", name)
+	}
+}
+"#;
+        assert!(code.contains(tuple), "{}", code);
+        let custom = r#"
+func (obj InstructionError__Custom) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	if content, err = json_encode_u32((uint32)(obj)); err != nil { return nil, err }
+	return jsonObject([]string{"Custom"}, []json.RawMessage{content}), nil
+}
+"#;
+        assert!(code.contains(custom), "{}", code);
+        // Enums are interfaces, written through their variants' methods.
+        let helpers = r#"
+func json_encode_InstructionError(value InstructionError) (json.RawMessage, error) {
+	return json.Marshal(value)
+}
+
+func json_decode_InstructionError(data json.RawMessage) (InstructionError, error) {
+	return UnmarshalJSONInstructionError(data)
+}
+"#;
+        assert!(code.contains(helpers), "{}", code);
+    }
+
+    #[test]
+    fn root_snapshot() {
+        let code = generate(Enums::Structs);
+        let root = r#"
+func (obj Meta) MarshalJSON() ([]byte, error) {
+	var content json.RawMessage
+	var err error
+	values := make([]json.RawMessage, 3)
+	if values[0], err = json_encode_Result(obj.Status); err != nil { return nil, err }
+	if values[1], err = json_encode_u64(obj.Fee); err != nil { return nil, err }
+	if values[2], err = json_encode_vector_CompiledInstruction(obj.Instructions); err != nil { return nil, err }
+	content = jsonObject([]string{"status", "fee", "instructions"}, values)
+	errValue := json.RawMessage("null")
+	if status, ok := obj.Status.(*Result__Err); ok {
+		if errValue, err = json_encode_TransactionError(status.Value); err != nil { return nil, err }
+	}
+	content = jsonPrepend("err", errValue, content)
+	return content, nil
+}
+
+func (target *Meta) UnmarshalJSON(content []byte) error {
+	var obj Meta
+	fields, err := jsonFields(content)
+	if err != nil { return fmt.Errorf("Meta: %w", err) }
+	if obj.Status, err = json_decode_Result(fields["status"]); err != nil { return fmt.Errorf("Meta.status: %w", err) }
+	if obj.Fee, err = json_decode_u64(fields["fee"]); err != nil { return fmt.Errorf("Meta.fee: %w", err) }
+	if obj.Instructions, err = json_decode_vector_CompiledInstruction(fields["instructions"]); err != nil { return fmt.Errorf("Meta.instructions: %w", err) }
+	*target = obj
+	return nil
+}
+"#;
+        assert!(code.contains(root), "{}", code);
+        // Only the root has an `err` field.
+        assert_eq!(code.matches("jsonPrepend(\"err\"").count(), 1);
+        assert!(code.starts_with("package p\n"));
+    }
+
+    #[test]
+    fn compiled_instruction_data_is_base58() {
+        let code = generate(Enums::Structs);
+        assert!(code.contains(
+            "\tif values[1], err = json_encode_base58(obj.Data); err != nil { return nil, err }\n"
+        ));
+        assert!(code.contains(
+            "\tif obj.Data, err = json_decode_base58(fields[\"data\"]); err != nil { return fmt.Errorf(\"CompiledInstruction.data: %w\", err) }\n"
+        ));
+    }
+
+    #[test]
+    fn integer_enums_snapshot() {
+        let code = generate(Enums::Integers);
+        let unit = r#"
+func (obj TransactionErrorUnit) MarshalJSON() ([]byte, error) {
+	switch obj {
+	case TransactionErrorAccountInUse:
+		return json.Marshal("AccountInUse")
+	default:
+		return nil, fmt.Errorf("unknown variant %d of TransactionError", uint32(obj))
+	}
+}
+"#;
+        assert!(code.contains(unit), "{}", code);
+        assert!(code
+            .contains("\tcase \"AccountInUse\":\n\t\treturn TransactionErrorAccountInUse, nil\n"));
+        assert!(!code.contains("TransactionError__AccountInUse"));
+    }
+
+    #[test]
+    fn unit_only_integer_enum() {
+        #[allow(dead_code)]
+        #[derive(Serialize, Deserialize)]
+        enum Flag {
+            On,
+            Off,
+        }
+        let mut tracer = Tracer::new(TracerConfig::default());
+        tracer.trace_type::<Flag>(&Samples::new()).unwrap();
+        let mut out = Vec::new();
+        output(
+            &mut out,
+            "p",
+            &tracer.registry().unwrap(),
+            "Flag",
+            Enums::Integers,
+        )
+        .unwrap();
+        let code = String::from_utf8(out).unwrap();
+        let expected = r#"
+func (obj Flag) MarshalJSON() ([]byte, error) {
+	switch obj {
+	case FlagOn:
+		return json.Marshal("On")
+	case FlagOff:
+		return json.Marshal("Off")
+	default:
+		return nil, fmt.Errorf("unknown variant %d of Flag", uint32(obj))
+	}
+}
+
+func (target *Flag) UnmarshalJSON(data []byte) error {
+	obj, err := UnmarshalJSONFlag(data)
+	if err != nil { return err }
+	*target = obj
+	return nil
+}
+
+// UnmarshalJSONFlag reads a Flag as written by its MarshalJSON methods.
+func UnmarshalJSONFlag(data []byte) (Flag, error) {
+	name, content, err := jsonVariant(data)
+	if err != nil { return 0, fmt.Errorf("Flag: %w", err) }
+	switch name {
+	case "On":
+		return FlagOn, nil
+	case "Off":
+		return FlagOff, nil
+	default:
+		return 0, fmt.Errorf("unknown variant %q of Flag", name)
+	}
+}
+"#;
+        assert_eq!(&code[HEADER.replace("{package}", "p").len()..], expected);
+    }
+
+    #[test]
+    fn unsupported_formats() {
+        #[allow(dead_code)]
+        #[derive(Serialize, Deserialize)]
+        struct Balances {
+            owners: std::collections::BTreeMap<u8, u64>,
+        }
+        let mut tracer = Tracer::new(TracerConfig::default());
+        tracer.trace_type::<Balances>(&Samples::new()).unwrap();
+        let registry = tracer.registry().unwrap();
+        assert!(matches!(
+            output(&mut Vec::new(), "p", &registry, "Balances", Enums::Structs),
+            Err(Error::Unsupported(Format::Map { .. }))
+        ));
+    }
+
+    #[test]
+    fn mangled_names() {
+        let format = Format::Option(Box::new(Format::Seq(Box::new(Format::U8))));
+        assert_eq!(mangle(&format), "option_vector_u8");
+        let format = Format::TupleArray {
+            content: Box::new(Format::U8),
+            size: 32,
+        };
+        assert_eq!(mangle(&format), "array32_u8_array");
+        let format = Format::Tuple(vec![Format::U8, Format::TypeName("Key".to_string())]);
+        assert_eq!(mangle(&format), "tuple2_u8_Key");
+    }
+}
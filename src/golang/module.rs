@@ -0,0 +1,175 @@
+/// The module path the generated Go module is published under, by default.
+pub const DEFAULT_PATH: &str = "github.com/gagliardetto/solana-tx-meta-parser-generators/go";
+
+/// Where serde-generate's own code imports its runtime from.
+pub const UPSTREAM_RUNTIME: &str =
+    "github.com/novifinancial/serde-reflection/serde-generate/runtime/golang";
+
+/// serde-generate's Go runtime, at 0.25.1, relative to the runtime directory.
+const RUNTIME: &[(&str, &str)] = &[
+    (
+        "serde/binary_deserializer.go",
+        include_str!("runtime/serde/binary_deserializer.go"),
+    ),
+    (
+        "serde/binary_serializer.go",
+        include_str!("runtime/serde/binary_serializer.go"),
+    ),
+    (
+        "serde/interfaces.go",
+        include_str!("runtime/serde/interfaces.go"),
+    ),
+    ("serde/types.go", include_str!("runtime/serde/types.go")),
+    (
+        "bincode/deserializer.go",
+        include_str!("runtime/bincode/deserializer.go"),
+    ),
+    (
+        "bincode/serializer.go",
+        include_str!("runtime/bincode/serializer.go"),
+    ),
+];
+
+/// The Go module the generated packages live in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub path: String,
+    /// The module path of the serde runtime, or `None` to vendor it at the
+    /// root of the module.
+    pub runtime: Option<String>,
+}
+
+impl Default for Module {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_PATH.to_string(),
+            runtime: None,
+        }
+    }
+}
+
+impl Module {
+    /// The path the generated code imports `serde` and `bincode` under.
+    pub fn runtime_path(&self) -> &str {
+        self.runtime.as_deref().unwrap_or(&self.path)
+    }
+
+    /// The contents of `go.mod`. An external runtime is left for `go get` to
+    /// resolve, since its version is not known here.
+    pub fn go_mod(&self) -> String {
+        format!("module {}\n\ngo 1.14\n", self.path)
+    }
+
+    /// The vendored runtime files, relative to the module root, importing each
+    /// other under this module. Empty with an external runtime.
+    pub fn runtime_files(&self) -> Vec<(&'static str, String)> {
+        if self.runtime.is_some() {
+            return Vec::new();
+        }
+        RUNTIME
+            .iter()
+            .map(|(path, contents)| (*path, contents.replace(UPSTREAM_RUNTIME, &self.path)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn external() -> Module {
+        Module {
+            path: "example.com/meta".to_string(),
+            runtime: Some("example.com/serde".to_string()),
+        }
+    }
+
+    #[test]
+    fn runtime_path() {
+        assert_eq!(Module::default().runtime_path(), DEFAULT_PATH);
+        assert_eq!(external().runtime_path(), "example.com/serde");
+    }
+
+    #[test]
+    fn go_mod_snapshot() {
+        assert_eq!(
+            Module::default().go_mod(),
+            "module github.com/gagliardetto/solana-tx-meta-parser-generators/go\n\ngo 1.14\n"
+        );
+        // No require line: the external runtime is resolved by `go get`.
+        assert_eq!(external().go_mod(), "module example.com/meta\n\ngo 1.14\n");
+    }
+
+    #[test]
+    fn vendored_runtime() {
+        let module = Module {
+            path: "example.com/meta".to_string(),
+            runtime: None,
+        };
+        let files = module.runtime_files();
+        let paths: Vec<_> = files.iter().map(|(path, _)| *path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "serde/binary_deserializer.go",
+                "serde/binary_serializer.go",
+                "serde/interfaces.go",
+                "serde/types.go",
+                "bincode/deserializer.go",
+                "bincode/serializer.go",
+            ]
+        );
+        for (path, contents) in &files {
+            let package = path.split('/').next().unwrap();
+            assert!(
+                contents.contains(&format!("\npackage {}\n", package)),
+                "{}",
+                path
+            );
+            assert!(!contents.contains(UPSTREAM_RUNTIME), "{}", path);
+        }
+        let imports = r#"
+import (
+	"errors"
+	"math"
+
+	"example.com/meta/serde"
+)
+"#;
+        assert!(files[4].1.contains(imports), "{}", files[4].1);
+    }
+
+    #[test]
+    fn external_runtime_is_not_vendored() {
+        assert!(external().runtime_files().is_empty());
+    }
+
+    /// serde-generate's code imports the runtime where `runtime_files` puts it.
+    #[test]
+    fn generated_code_imports_the_vendored_runtime() {
+        #[allow(dead_code)]
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Fee(u64);
+        let mut tracer = serde_reflection::Tracer::new(serde_reflection::TracerConfig::default());
+        tracer
+            .trace_type::<Fee>(&serde_reflection::Samples::new())
+            .unwrap();
+        let module = Module::default();
+        let config = serde_generate::CodeGeneratorConfig::new("p".to_string())
+            .with_encodings(vec![serde_generate::Encoding::Bincode]);
+        let mut source = Vec::new();
+        serde_generate::golang::CodeGenerator::new(&config)
+            .with_serde_module_path(module.runtime_path().to_string())
+            .output(&mut source, &tracer.registry().unwrap())
+            .unwrap();
+        let source = String::from_utf8(source).unwrap();
+        for (path, _) in module.runtime_files() {
+            let package = path.split('/').next().unwrap();
+            assert!(
+                source.contains(&format!("\"{}/{}\"\n", DEFAULT_PATH, package)),
+                "{}",
+                source
+            );
+        }
+    }
+}
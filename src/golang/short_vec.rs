@@ -0,0 +1,116 @@
+use super::quote_name;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("field {0} is not a vector of bytes in the generated Go code")]
+    NotAByteVector(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Makes the generated code encode the `short_vec` `fields`, given as
+/// `Container.field`, with a ShortU16 length instead of a u64 one.
+pub fn apply(source: &str, fields: &[String]) -> Result<String> {
+    let mut source = source.to_string();
+    for field in fields {
+        let not_found = || Error::NotAByteVector(field.to_string());
+        let (container, name) = field.split_once('.').ok_or_else(not_found)?;
+        let name = quote_name(name);
+        let serialize = format!("func (obj *{}) Serialize(", container);
+        let deserialize = format!("func Deserialize{}(", container);
+        for (function, generated, replacement) in [
+            (
+                &serialize,
+                format!("serialize_vector_u8(obj.{}, ", name),
+                format!("serialize_short_vec_u8(obj.{}, ", name),
+            ),
+            (
+                &deserialize,
+                format!(
+                    "deserialize_vector_u8(deserializer); err == nil {{ obj.{} = ",
+                    name
+                ),
+                format!(
+                    "deserialize_short_vec_u8(deserializer); err == nil {{ obj.{} = ",
+                    name
+                ),
+            ),
+        ] {
+            let start = source.find(function.as_str()).ok_or_else(not_found)?;
+            let end = start + source[start..].find("\n}\n").ok_or_else(not_found)?;
+            let body = &source[start..end];
+            if !body.contains(&generated) {
+                return Err(not_found());
+            }
+            let body = body.replace(&generated, &replacement);
+            source.replace_range(start..end, &body);
+        }
+    }
+    if !fields.is_empty() {
+        source.push_str(HELPERS);
+    }
+    Ok(source)
+}
+
+// From https://github.com/solana-labs/solana/blob/v1.14.18/sdk/program/src/short_vec.rs
+const HELPERS: &str = r#"
+// serialize_short_vec_u8 writes the length as a ShortU16: 7 bits per byte,
+// least significant first, with the high bit set on all but the last byte.
+func serialize_short_vec_u8(value []uint8, serializer serde.Serializer) error {
+	if len(value) > 0xffff {
+		return fmt.Errorf("short_vec length %d does not fit in a u16", len(value))
+	}
+	rem := len(value)
+	for {
+		elem := uint8(rem & 0x7f)
+		rem >>= 7
+		if rem == 0 {
+			if err := serializer.SerializeU8(elem); err != nil { return err }
+			break
+		}
+		if err := serializer.SerializeU8(elem | 0x80); err != nil { return err }
+	}
+	for _, item := range value {
+		if err := serializer.SerializeU8(item); err != nil { return err }
+	}
+	return nil
+}
+
+// deserialize_short_vec_len rejects the same encodings as the Rust ShortU16Visitor.
+func deserialize_short_vec_len(deserializer serde.Deserializer) (int, error) {
+	length := 0
+	for nth := 0; nth < 3; nth++ {
+		elem, err := deserializer.DeserializeU8()
+		if err != nil { return 0, err }
+		if elem == 0 && nth != 0 {
+			return 0, fmt.Errorf("short_vec length: alias encoding")
+		}
+		if nth == 2 && elem&0x80 != 0 {
+			return 0, fmt.Errorf("short_vec length: continue signal on byte-three")
+		}
+		length |= int(elem&0x7f) << (7 * nth)
+		if length > 0xffff {
+			return 0, fmt.Errorf("short_vec length %d does not fit in a u16", length)
+		}
+		if elem&0x80 == 0 {
+			return length, nil
+		}
+	}
+	panic("unreachable")
+}
+
+func deserialize_short_vec_u8(deserializer serde.Deserializer) ([]uint8, error) {
+	length, err := deserialize_short_vec_len(deserializer)
+	if err != nil { return nil, err }
+	// The limits file of the package defines limitedDeserializer.
+	if limited, ok := deserializer.(*limitedDeserializer); ok {
+		if err := limited.checkLength(uint64(length)); err != nil { return nil, err }
+	}
+	obj := make([]uint8, length)
+	for i := range obj {
+		if val, err := deserializer.DeserializeU8(); err == nil { obj[i] = val } else { return nil, err }
+	}
+	return obj, nil
+}
+"#;
@@ -0,0 +1,586 @@
+use super::enums::{self, Enums};
+use super::{quote_name, quote_type};
+use crate::rpc;
+use crate::vectors::Fixtures;
+use serde_json::Value;
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
+use std::io::Write;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("container {0} is not in the registry")]
+    MissingContainer(String),
+    #[error("{0} does not match {1:?}")]
+    Mismatch(Value, Format),
+    #[error("no Go literal for {0:?}")]
+    Unsupported(Format),
+    #[error("RPC rendering error: {0}")]
+    Rpc(#[from] crate::rpc::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Writes a Go test that decodes every vector of `fixtures` with the bindings
+/// generated for `registry`, compares the result with the expected value, and
+/// checks that encoding it again gives back the same bytes, and that it
+/// marshals to its RPC rendering and back. Each vector also decodes within
+/// its own depth and fails with a `*LimitError` one level short of it.
+pub fn output(
+    out: &mut dyn Write,
+    package: &str,
+    registry: &Registry,
+    short_vec: &[String],
+    enums: Enums,
+    fixtures: &Fixtures,
+) -> Result<()> {
+    let literals = Literals {
+        registry,
+        short_vec,
+        enums,
+    };
+    let root = Format::TypeName(fixtures.root.clone());
+    let mut cases = Vec::new();
+    for vector in &fixtures.vectors {
+        let expected = literals.literal(&root, &vector.json)?;
+        let rpc = rpc::render(registry, &fixtures.root, short_vec, &vector.json)?;
+        cases.push((vector, expected, rpc));
+    }
+
+    writeln!(out, "package {}", package)?;
+    writeln!(out)?;
+    writeln!(out, "import (")?;
+    writeln!(out, "\t\"bytes\"")?;
+    writeln!(out, "\t\"encoding/hex\"")?;
+    writeln!(out, "\t\"encoding/json\"")?;
+    writeln!(out, "\t\"errors\"")?;
+    writeln!(out, "\t\"reflect\"")?;
+    writeln!(out, "\t\"testing\"")?;
+    writeln!(out, ")")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "// Vectors produced by the Rust definitions of {}.",
+        fixtures.commit
+    )?;
+    writeln!(out, "func TestGoldenVectors(t *testing.T) {{")?;
+    writeln!(out, "\tvectors := []struct {{")?;
+    writeln!(out, "\t\tname     string")?;
+    writeln!(out, "\t\tbincode  string")?;
+    writeln!(out, "\t\texpected {}", fixtures.root)?;
+    writeln!(out, "\t\trpc      string")?;
+    writeln!(out, "\t\tdepth    uint64")?;
+    writeln!(out, "\t}}{{")?;
+    for (vector, expected, rpc) in cases {
+        writeln!(out, "\t\t{{")?;
+        writeln!(out, "\t\t\tname:     {},", quote_string(&vector.name))?;
+        writeln!(out, "\t\t\tbincode:  \"{}\",", vector.bincode)?;
+        writeln!(out, "\t\t\texpected: {},", expected)?;
+        writeln!(out, "\t\t\trpc:      {},", quote_string(&rpc.to_string()))?;
+        writeln!(out, "\t\t\tdepth:    {},", vector.depth)?;
+        writeln!(out, "\t\t}},")?;
+    }
+    writeln!(out, "\t}}")?;
+    write!(
+        out,
+        r#"	for _, vector := range vectors {{
+		vector := vector
+		t.Run(vector.name, func(t *testing.T) {{
+			input, err := hex.DecodeString(vector.bincode)
+			if err != nil {{
+				t.Fatal(err)
+			}}
+			obj, err := BincodeDeserialize{0}(input)
+			if err != nil {{
+				t.Fatalf("decode: %v", err)
+			}}
+			if !reflect.DeepEqual(obj, vector.expected) {{
+				t.Fatalf("decoded %#v, expected %#v", obj, vector.expected)
+			}}
+			output, err := obj.BincodeSerialize()
+			if err != nil {{
+				t.Fatalf("encode: %v", err)
+			}}
+			if !bytes.Equal(output, input) {{
+				t.Fatalf("encoded %x, expected %x", output, input)
+			}}
+			rendered, err := obj.MarshalJSON()
+			if err != nil {{
+				t.Fatalf("marshal: %v", err)
+			}}
+			if !jsonEqual(t, rendered, []byte(vector.rpc)) {{
+				t.Fatalf("marshalled %s, expected %s", rendered, vector.rpc)
+			}}
+			var unmarshalled {0}
+			if err := unmarshalled.UnmarshalJSON(rendered); err != nil {{
+				t.Fatalf("unmarshal: %v", err)
+			}}
+			if !reflect.DeepEqual(unmarshalled, obj) {{
+				t.Fatalf("unmarshalled %#v, expected %#v", unmarshalled, obj)
+			}}
+			for _, depth := range []uint64{{vector.depth, vector.depth - 1}} {{
+				limits := DefaultDecodeLimits
+				limits.MaxContainerDepth = depth
+				deserializer, err := NewLimitedDeserializer(input, limits)
+				if err != nil {{
+					t.Fatal(err)
+				}}
+				_, err = Deserialize{0}(deserializer)
+				var limitErr *LimitError
+				if depth == vector.depth && err != nil {{
+					t.Fatalf("decode within a depth of %d: %v", depth, err)
+				}}
+				if depth < vector.depth && !errors.As(err, &limitErr) {{
+					t.Fatalf("decode within a depth of %d: expected a limit error, got %v", depth, err)
+				}}
+			}}
+		}})
+	}}
+}}
+
+// jsonEqual compares JSON documents, keeping numbers as written.
+func jsonEqual(t *testing.T, a, b []byte) bool {{
+	var values [2]interface{{}}
+	for i, data := range [][]byte{{a, b}} {{
+		decoder := json.NewDecoder(bytes.NewReader(data))
+		decoder.UseNumber()
+		if err := decoder.Decode(&values[i]); err != nil {{
+			t.Fatalf("invalid JSON %s: %v", data, err)
+		}}
+	}}
+	return reflect.DeepEqual(values[0], values[1])
+}}
+"#,
+        fixtures.root
+    )?;
+    Ok(())
+}
+
+/// Builds Go literals of the bindings generated for `registry`.
+struct Literals<'a> {
+    registry: &'a Registry,
+    /// Fields declared with `with = "short_vec"`, as `Container.field`.
+    short_vec: &'a [String],
+    enums: Enums,
+}
+
+impl<'a> Literals<'a> {
+    /// A Go expression of type `quote_type(format)` equal to the serde_json `value`.
+    fn literal(&self, format: &Format, value: &Value) -> Result<String> {
+        let mismatch = || Error::Mismatch(value.clone(), format.clone());
+        let literal = match format {
+            Format::TypeName(name) => self.container_literal(name, value)?,
+            Format::Unit => "struct {}{}".to_string(),
+            Format::Bool => value.as_bool().ok_or_else(mismatch)?.to_string(),
+            Format::I8
+            | Format::I16
+            | Format::I32
+            | Format::I64
+            | Format::U8
+            | Format::U16
+            | Format::U32
+            | Format::U64
+            | Format::F32
+            | Format::F64 => match value {
+                Value::Number(number) => number.to_string(),
+                _ => return Err(mismatch()),
+            },
+            Format::Str => quote_string(value.as_str().ok_or_else(mismatch)?),
+            Format::Bytes => {
+                let bytes = self.seq_literal(&Format::U8, value)?;
+                format!("[]byte{{{}}}", bytes.join(", "))
+            }
+            Format::Option(inner) => match value {
+                Value::Null => "nil".to_string(),
+                value => pointer_to(inner, &self.literal(inner, value)?),
+            },
+            Format::Seq(inner) => {
+                let items = self.seq_literal(inner, value)?;
+                format!("{}{{{}}}", quote_type(format), items.join(", "))
+            }
+            Format::Tuple(formats) => {
+                let fields = self.tuple_fields(formats, value)?;
+                format!("{}{{{}}}", quote_type(format), fields.join(", "))
+            }
+            Format::TupleArray { content, .. } => {
+                let items = self.seq_literal(content, value)?;
+                format!("{}{{{}}}", quote_type(format), items.join(", "))
+            }
+            Format::I128 | Format::U128 | Format::Char | Format::Map { .. } => {
+                return Err(Error::Unsupported(format.clone()))
+            }
+            Format::Variable(_) => unreachable!("registry contains unresolved formats"),
+        };
+        Ok(literal)
+    }
+
+    fn container_literal(&self, name: &str, value: &Value) -> Result<String> {
+        let container = self
+            .registry
+            .get(name)
+            .ok_or_else(|| Error::MissingContainer(name.to_string()))?;
+        let format = Format::TypeName(name.to_string());
+        let literal = match container {
+            ContainerFormat::UnitStruct => format!("{}{{}}", name),
+            ContainerFormat::NewTypeStruct(inner) => self.newtype_literal(name, inner, value)?,
+            ContainerFormat::TupleStruct(formats) => {
+                let fields = self.tuple_fields(formats, value)?;
+                format!("{}{{{}}}", name, fields.join(", "))
+            }
+            ContainerFormat::Struct(fields) => {
+                let fields = self.struct_fields(name, fields, value)?;
+                format!("{}{{{}}}", name, fields.join(", "))
+            }
+            ContainerFormat::Enum(variants) => {
+                // serde_json writes unit variants as strings and the others as single-key objects.
+                let (variant_name, content) = match value {
+                    Value::String(variant_name) => (variant_name, &Value::Null),
+                    Value::Object(map) if map.len() == 1 => map.iter().next().unwrap(),
+                    _ => return Err(Error::Mismatch(value.clone(), format)),
+                };
+                let variant = variants
+                    .values()
+                    .find(|variant| &variant.name == variant_name)
+                    .ok_or_else(|| Error::Mismatch(value.clone(), format.clone()))?;
+                let full_name = format!("{}__{}", name, quote_name(&variant.name));
+                match &variant.value {
+                    VariantFormat::Unit => match self.enums {
+                        Enums::Structs => format!("&{}{{}}", full_name),
+                        Enums::Integers => enums::constant(name, &variant.name),
+                    },
+                    VariantFormat::NewType(inner) => match inner.as_ref() {
+                        Format::TypeName(_) | Format::Option(_) => {
+                            format!("&{}", self.newtype_literal(&full_name, inner, content)?)
+                        }
+                        Format::Unit => format!("&{}{{}}", full_name),
+                        _ => {
+                            pointer_to(&Format::TypeName(full_name), &self.literal(inner, content)?)
+                        }
+                    },
+                    VariantFormat::Tuple(formats) => {
+                        let fields = self.tuple_fields(formats, content)?;
+                        format!("&{}{{{}}}", full_name, fields.join(", "))
+                    }
+                    VariantFormat::Struct(fields) => {
+                        let fields = self.struct_fields(&full_name, fields, content)?;
+                        format!("&{}{{{}}}", full_name, fields.join(", "))
+                    }
+                    VariantFormat::Variable(_) => {
+                        unreachable!("registry contains unresolved variants")
+                    }
+                }
+            }
+        };
+        Ok(literal)
+    }
+
+    /// serde-generate wraps type names and options in a `Value` field and turns
+    /// every other newtype into a Go type definition.
+    fn newtype_literal(&self, name: &str, inner: &Format, value: &Value) -> Result<String> {
+        let literal = self.literal(inner, value)?;
+        Ok(match inner {
+            Format::TypeName(_) | Format::Option(_) => format!("{}{{Value: {}}}", name, literal),
+            _ => format!("{}({})", name, literal),
+        })
+    }
+
+    fn struct_fields(
+        &self,
+        name: &str,
+        fields: &[Named<Format>],
+        value: &Value,
+    ) -> Result<Vec<String>> {
+        let mut literals = Vec::new();
+        for field in fields {
+            let mut content = value.get(&field.name).unwrap_or(&Value::Null).clone();
+            // serde_json writes a short_vec as its length, itself a tuple of bytes, followed by the items.
+            let path = format!("{}.{}", name, field.name);
+            if self.short_vec.contains(&path) {
+                match content.as_array_mut() {
+                    Some(items) if !items.is_empty() => {
+                        items.remove(0);
+                    }
+                    _ => return Err(Error::Mismatch(content, field.value.clone())),
+                }
+            }
+            literals.push(format!(
+                "{}: {}",
+                quote_name(&field.name),
+                self.literal(&field.value, &content)?
+            ));
+        }
+        Ok(literals)
+    }
+
+    fn tuple_fields(&self, formats: &[Format], value: &Value) -> Result<Vec<String>> {
+        let values = match value {
+            Value::Array(values) if values.len() == formats.len() => values,
+            _ => {
+                return Err(Error::Mismatch(
+                    value.clone(),
+                    Format::Tuple(formats.to_vec()),
+                ))
+            }
+        };
+        let mut literals = Vec::new();
+        for (index, (format, value)) in formats.iter().zip(values).enumerate() {
+            literals.push(format!("Field{}: {}", index, self.literal(format, value)?));
+        }
+        Ok(literals)
+    }
+
+    fn seq_literal(&self, format: &Format, value: &Value) -> Result<Vec<String>> {
+        let values = value
+            .as_array()
+            .ok_or_else(|| Error::Mismatch(value.clone(), format.clone()))?;
+        values
+            .iter()
+            .map(|value| self.literal(format, value))
+            .collect()
+    }
+}
+
+/// Go cannot take the address of a conversion or of a basic literal, hence the closure.
+fn pointer_to(format: &Format, literal: &str) -> String {
+    let go_type = quote_type(format);
+    format!(
+        "func() *{0} {{ v := ({0})({1}); return &v }}()",
+        go_type, literal
+    )
+}
+
+fn quote_string(value: &str) -> String {
+    // JSON string escapes are valid in Go interpreted string literals.
+    serde_json::to_string(value).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use serde_reflection::{Samples, Tracer, TracerConfig};
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    enum Failure {
+        Unknown,
+        Custom(u32),
+        Account { index: u8 },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Fee(u64);
+
+    #[derive(Serialize, Deserialize)]
+    struct Meta {
+        status: std::result::Result<(), Failure>,
+        fee: Fee,
+        memo: Option<String>,
+        key: [u8; 2],
+        balances: Vec<(u8, u64)>,
+    }
+
+    fn registry() -> Registry {
+        let mut tracer = Tracer::new(TracerConfig::default());
+        let samples = Samples::new();
+        tracer.trace_type::<Failure>(&samples).unwrap();
+        tracer
+            .trace_type::<std::result::Result<(), Failure>>(&samples)
+            .unwrap();
+        tracer.trace_type::<Meta>(&samples).unwrap();
+        tracer.registry().unwrap()
+    }
+
+    fn fixtures() -> Fixtures {
+        let mut fixtures = Fixtures::new("abc", "Meta");
+        fixtures
+            .push(
+                "Err",
+                &Meta {
+                    status: Err(Failure::Account { index: 1 }),
+                    fee: Fee(5000),
+                    memo: Some("a \"memo\"".to_string()),
+                    key: [1, 2],
+                    balances: vec![(0, 10)],
+                },
+            )
+            .unwrap();
+        fixtures
+    }
+
+    fn literals<'a>(registry: &'a Registry, short_vec: &'a [String], enums: Enums) -> Literals<'a> {
+        Literals {
+            registry,
+            short_vec,
+            enums,
+        }
+    }
+
+    fn type_name(name: &str) -> Format {
+        Format::TypeName(name.to_string())
+    }
+
+    #[test]
+    fn output_snapshot() {
+        let mut out = Vec::new();
+        output(&mut out, "p", &registry(), &[], Enums::Structs, &fixtures()).unwrap();
+        let code = String::from_utf8(out).unwrap();
+        let expected = r#"package p
+
+import (
+	"bytes"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"reflect"
+	"testing"
+)
+
+// Vectors produced by the Rust definitions of abc.
+func TestGoldenVectors(t *testing.T) {
+	vectors := []struct {
+		name     string
+		bincode  string
+		expected Meta
+		rpc      string
+		depth    uint64
+	}{
+		{
+			name:     "Err",
+			bincode:  "01000000020000000188130000000000000108000000000000006120226d656d6f2201020100000000000000000a00000000000000",
+			expected: Meta{Status: &Result__Err{Value: &Failure__Account{Index: 1}}, Fee: Fee(5000), Memo: func() *string { v := (string)("a \"memo\""); return &v }(), Key: [2]uint8{1, 2}, Balances: []struct {Field0 uint8; Field1 uint64}{struct {Field0 uint8; Field1 uint64}{Field0: 0, Field1: 10}}},
+			rpc:      "{\"balances\":[[0,10]],\"err\":{\"Account\":{\"index\":1}},\"fee\":5000,\"key\":[1,2],\"memo\":\"a \\\"memo\\\"\",\"status\":{\"Err\":{\"Account\":{\"index\":1}}}}",
+			depth:    3,
+		},
+	}
+	for _, vector := range vectors {
+"#;
+        assert!(code.starts_with(expected), "{}", code);
+        assert!(code.contains("\t\t\tobj, err := BincodeDeserializeMeta(input)\n"));
+        assert!(code.contains("\t\t\tvar unmarshalled Meta\n"));
+        assert!(code
+            .contains("\t\t\tfor _, depth := range []uint64{vector.depth, vector.depth - 1} {\n"));
+        assert!(code.contains("\t\t\t\t_, err = DeserializeMeta(deserializer)\n"));
+        assert!(code.ends_with("\treturn reflect.DeepEqual(values[0], values[1])\n}\n"));
+    }
+
+    #[test]
+    fn enum_literals() {
+        let registry = registry();
+        let failure = type_name("Failure");
+        let structs = literals(&registry, &[], Enums::Structs);
+        let integers = literals(&registry, &[], Enums::Integers);
+        let cases = [
+            (json!("Unknown"), "&Failure__Unknown{}", "FailureUnknown"),
+            (
+                json!({"Custom": 7}),
+                "func() *Failure__Custom { v := (Failure__Custom)(7); return &v }()",
+                "func() *Failure__Custom { v := (Failure__Custom)(7); return &v }()",
+            ),
+            (
+                json!({"Account": {"index": 2}}),
+                "&Failure__Account{Index: 2}",
+                "&Failure__Account{Index: 2}",
+            ),
+        ];
+        for (value, as_struct, as_integer) in cases {
+            assert_eq!(structs.literal(&failure, &value).unwrap(), as_struct);
+            assert_eq!(integers.literal(&failure, &value).unwrap(), as_integer);
+        }
+        assert_eq!(
+            structs
+                .literal(&type_name("Result"), &json!({"Ok": null}))
+                .unwrap(),
+            "&Result__Ok{}"
+        );
+    }
+
+    #[test]
+    fn value_literals() {
+        let registry = registry();
+        let literals = literals(&registry, &[], Enums::Structs);
+        let u8s = Format::Seq(Box::new(Format::U8));
+        let cases = [
+            (Format::Bool, json!(true), "true"),
+            (Format::I64, json!(-3), "-3"),
+            (Format::Str, json!("tab\there"), "\"tab\\there\""),
+            (Format::Bytes, json!([1, 2]), "[]byte{1, 2}"),
+            (u8s.clone(), json!([]), "[]uint8{}"),
+            (Format::Option(Box::new(u8s.clone())), json!(null), "nil"),
+            (
+                Format::Option(Box::new(u8s)),
+                json!([3]),
+                "func() *[]uint8 { v := ([]uint8)([]uint8{3}); return &v }()",
+            ),
+            (
+                Format::Option(Box::new(type_name("Fee"))),
+                json!(1),
+                "func() *Fee { v := (Fee)(Fee(1)); return &v }()",
+            ),
+            (Format::Unit, json!(null), "struct {}{}"),
+        ];
+        for (format, value, expected) in cases {
+            assert_eq!(literals.literal(&format, &value).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn short_vec_length_is_skipped() {
+        let registry = registry();
+        let short_vec = ["Meta.balances".to_string()];
+        let literals = literals(&registry, &short_vec, Enums::Structs);
+        let value = json!({
+            "status": {"Ok": null},
+            "fee": 0,
+            "memo": null,
+            "key": [0, 0],
+            "balances": [[1], [4, 5]],
+        });
+        assert_eq!(
+            literals.literal(&type_name("Meta"), &value).unwrap(),
+            "Meta{Status: &Result__Ok{}, Fee: Fee(0), Memo: nil, Key: [2]uint8{0, 0}, Balances: []struct {Field0 uint8; Field1 uint64}{struct {Field0 uint8; Field1 uint64}{Field0: 4, Field1: 5}}}"
+        );
+        let mut value = value;
+        value["balances"] = json!([]);
+        assert!(matches!(
+            literals.literal(&type_name("Meta"), &value),
+            Err(Error::Mismatch(..))
+        ));
+    }
+
+    #[test]
+    fn mismatches() {
+        let registry = registry();
+        let literals = literals(&registry, &[], Enums::Structs);
+        assert!(matches!(
+            literals.literal(&Format::U8, &json!("1")),
+            Err(Error::Mismatch(..))
+        ));
+        assert!(matches!(
+            literals.literal(&type_name("Failure"), &json!({"Other": 1})),
+            Err(Error::Mismatch(..))
+        ));
+        assert!(matches!(
+            literals.literal(
+                &type_name("Failure"),
+                &json!({"Custom": 1, "Unknown": null})
+            ),
+            Err(Error::Mismatch(..))
+        ));
+        assert!(matches!(
+            literals.literal(&Format::Tuple(vec![Format::U8, Format::U8]), &json!([1])),
+            Err(Error::Mismatch(..))
+        ));
+        assert!(matches!(
+            literals.literal(&type_name("Missing"), &json!(null)),
+            Err(Error::MissingContainer(name)) if name == "Missing"
+        ));
+        assert!(matches!(
+            literals.literal(&Format::Char, &json!("a")),
+            Err(Error::Unsupported(Format::Char))
+        ));
+    }
+}
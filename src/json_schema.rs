@@ -0,0 +1,423 @@
+use serde_json::{json, Map, Value};
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("container {0} is not in the registry")]
+    MissingContainer(String),
+    #[error("container {0} is not a struct")]
+    NotAStruct(String),
+    #[error("field {0} is not a vector")]
+    NotAVector(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Describes the `serde_json` encoding of `root`, with one `$defs` entry per container of `registry`.
+///
+/// Field and variant names are taken from the registry, so they already carry
+/// any `#[serde(rename_all = "camelCase")]` renaming. The `short_vec` fields,
+/// given as `Container.field`, start with their length.
+pub fn for_registry(
+    id: &str,
+    registry: &Registry,
+    root: &str,
+    short_vec: &[String],
+) -> Result<Value> {
+    if !registry.contains_key(root) {
+        return Err(Error::MissingContainer(root.to_string()));
+    }
+    let mut defs: Map<String, Value> = registry
+        .iter()
+        .map(|(name, container)| (name.clone(), container_schema(name, container)))
+        .collect();
+    for field in short_vec {
+        let not_a_vector = || Error::NotAVector(field.to_string());
+        let (container, name) = field.split_once('.').ok_or_else(not_a_vector)?;
+        let schema = defs
+            .get_mut(container)
+            .and_then(|schema| schema.get_mut("properties"))
+            .and_then(|properties| properties.get_mut(name))
+            .filter(|schema| schema.get("items").is_some())
+            .ok_or_else(not_a_vector)?;
+        // serde_json writes the ShortU16 length as a tuple of one to three bytes.
+        schema["prefixItems"] = json!([{
+            "type": "array",
+            "items": format_schema(&Format::U8),
+            "minItems": 1,
+            "maxItems": 3,
+        }]);
+        schema["minItems"] = json!(1);
+    }
+    Ok(json!({
+        "$schema": DRAFT,
+        "$id": id,
+        "$ref": type_ref(root),
+        "$defs": defs,
+    }))
+}
+
+/// Describes a `TransactionStatusMeta` as rendered by the RPC `UiTransactionStatusMeta`.
+///
+/// On top of the plain encoding the RPC adds an `err` field mirroring the error of
+/// `status`, and renders compiled instructions with plain account index arrays and
+/// base58-encoded data.
+pub fn rpc(id: &str, registry: &Registry) -> Result<Value> {
+    let mut schema = for_registry(id, registry, "TransactionStatusMeta", &[])?;
+    let defs = schema["$defs"].as_object_mut().unwrap();
+
+    let meta = defs["TransactionStatusMeta"].as_object_mut().unwrap();
+    let err = json!({
+        "anyOf": [{ "type": "null" }, { "$ref": type_ref("TransactionError") }],
+    });
+    meta["properties"]
+        .as_object_mut()
+        .unwrap()
+        .insert("err".to_string(), err);
+    meta["required"]
+        .as_array_mut()
+        .unwrap()
+        .insert(0, json!("err"));
+
+    if let Some(instruction) = defs.get_mut("CompiledInstruction") {
+        let properties = instruction
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+            .ok_or_else(|| Error::NotAStruct("CompiledInstruction".to_string()))?;
+        properties.insert("accounts".to_string(), format_schema(&seq(Format::U8)));
+        properties.insert(
+            "data".to_string(),
+            json!({ "type": "string", "contentEncoding": "base58" }),
+        );
+    }
+    Ok(schema)
+}
+
+fn container_schema(name: &str, container: &ContainerFormat) -> Value {
+    let mut schema = match container {
+        ContainerFormat::UnitStruct => json!({ "type": "null" }),
+        ContainerFormat::NewTypeStruct(format) => format_schema(format),
+        ContainerFormat::TupleStruct(formats) => tuple_schema(formats),
+        ContainerFormat::Struct(fields) => struct_schema(fields),
+        ContainerFormat::Enum(variants) => {
+            let variants: Vec<Value> = variants
+                .values()
+                .map(|variant| variant_schema(&variant.name, &variant.value))
+                .collect();
+            json!({ "oneOf": variants })
+        }
+    };
+    schema["title"] = json!(name);
+    schema
+}
+
+/// Enums use serde's externally tagged representation: unit variants are plain
+/// strings, every other variant is an object with the variant name as its only key.
+fn variant_schema(name: &str, variant: &VariantFormat) -> Value {
+    let content = match variant {
+        VariantFormat::Unit => return json!({ "const": name }),
+        VariantFormat::NewType(format) => format_schema(format),
+        VariantFormat::Tuple(formats) => tuple_schema(formats),
+        VariantFormat::Struct(fields) => struct_schema(fields),
+        VariantFormat::Variable(_) => unreachable!("registry contains unresolved variants"),
+    };
+    json!({
+        "type": "object",
+        "properties": { name: content },
+        "required": [name],
+        "additionalProperties": false,
+    })
+}
+
+fn struct_schema(fields: &[Named<Format>]) -> Value {
+    let properties: Map<String, Value> = fields
+        .iter()
+        .map(|field| (field.name.clone(), format_schema(&field.value)))
+        .collect();
+    let required: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn tuple_schema(formats: &[Format]) -> Value {
+    let items: Vec<Value> = formats.iter().map(format_schema).collect();
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "minItems": formats.len(),
+        "maxItems": formats.len(),
+    })
+}
+
+fn format_schema(format: &Format) -> Value {
+    match format {
+        Format::TypeName(name) => json!({ "$ref": type_ref(name) }),
+        Format::Unit => json!({ "type": "null" }),
+        Format::Bool => json!({ "type": "boolean" }),
+        Format::I8 => integer(i8::MIN, i8::MAX),
+        Format::I16 => integer(i16::MIN, i16::MAX),
+        Format::I32 => integer(i32::MIN, i32::MAX),
+        Format::I64 => integer(i64::MIN, i64::MAX),
+        Format::U8 => integer(u8::MIN, u8::MAX),
+        Format::U16 => integer(u16::MIN, u16::MAX),
+        Format::U32 => integer(u32::MIN, u32::MAX),
+        Format::U64 => integer(u64::MIN, u64::MAX),
+        // serde_json writes 128-bit integers as numbers, but not every JSON parser can hold them.
+        Format::I128 | Format::U128 => json!({ "type": "integer" }),
+        Format::F32 | Format::F64 => json!({ "type": "number" }),
+        Format::Char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+        Format::Str => json!({ "type": "string" }),
+        Format::Bytes => format_schema(&seq(Format::U8)),
+        Format::Option(format) => json!({
+            "anyOf": [{ "type": "null" }, format_schema(format)],
+        }),
+        Format::Seq(format) => json!({ "type": "array", "items": format_schema(format) }),
+        Format::Map { value, .. } => json!({
+            "type": "object",
+            "additionalProperties": format_schema(value),
+        }),
+        Format::Tuple(formats) => tuple_schema(formats),
+        Format::TupleArray { content, size } => json!({
+            "type": "array",
+            "items": format_schema(content),
+            "minItems": size,
+            "maxItems": size,
+        }),
+        Format::Variable(_) => unreachable!("registry contains unresolved formats"),
+    }
+}
+
+fn integer<T: Into<Value>>(minimum: T, maximum: T) -> Value {
+    json!({ "type": "integer", "minimum": minimum.into(), "maximum": maximum.into() })
+}
+
+fn seq(format: Format) -> Format {
+    Format::Seq(Box::new(format))
+}
+
+fn type_ref(name: &str) -> String {
+    format!("#/$defs/{}", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::{b7b4, ce598};
+    use crate::rpc;
+    use crate::schema::Schema;
+    use crate::vectors::Fixtures;
+
+    /// Checks `value` against `schema`, resolving `$ref`s in `root`. Only the
+    /// keywords emitted above are known; `contentEncoding` is checked for base58.
+    fn check(
+        root: &Value,
+        schema: &Value,
+        value: &Value,
+        path: &str,
+    ) -> std::result::Result<(), String> {
+        let fail = |why: &str| Err(format!("{}: {} ({})", path, why, value));
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.strip_prefix("#/$defs/").unwrap();
+            check(root, &root["$defs"][name], value, path)?;
+        }
+        if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+            let matches = match expected {
+                "null" => value.is_null(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "string" => value.is_string(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                _ => panic!("unknown type {}", expected),
+            };
+            if !matches {
+                return fail(&format!("not of type {}", expected));
+            }
+        }
+        let integer = |value: &Value| {
+            value
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| value.as_u64().map(i128::from))
+        };
+        if let (Some(minimum), Some(value)) = (schema.get("minimum"), integer(value)) {
+            if value < integer(minimum).unwrap() {
+                return fail("under the minimum");
+            }
+        }
+        if let (Some(maximum), Some(value)) = (schema.get("maximum"), integer(value)) {
+            if value > integer(maximum).unwrap() {
+                return fail("over the maximum");
+            }
+        }
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                return fail(&format!("not {}", constant));
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+            let matching = schemas
+                .iter()
+                .filter(|schema| check(root, schema, value, path).is_ok())
+                .count();
+            if matching != 1 {
+                return fail(&format!("{} oneOf alternatives match", matching));
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+            if !schemas
+                .iter()
+                .any(|schema| check(root, schema, value, path).is_ok())
+            {
+                return fail("no anyOf alternative matches");
+            }
+        }
+        if schema.get("contentEncoding").and_then(Value::as_str) == Some("base58")
+            && bs58::decode(value.as_str().unwrap_or_default())
+                .into_vec()
+                .is_err()
+        {
+            return fail("not base58");
+        }
+        if let Value::Object(fields) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for required in schema["required"].as_array().into_iter().flatten() {
+                if !fields.contains_key(required.as_str().unwrap()) {
+                    return fail(&format!("missing {}", required));
+                }
+            }
+            for (name, field) in fields {
+                let path = format!("{}.{}", path, name);
+                match (
+                    properties.and_then(|p| p.get(name)),
+                    schema.get("additionalProperties"),
+                ) {
+                    (Some(property), _) => check(root, property, field, &path)?,
+                    (None, Some(Value::Bool(false))) => {
+                        return Err(format!("{}: not allowed", path))
+                    }
+                    (None, Some(additional)) => check(root, additional, field, &path)?,
+                    (None, None) => (),
+                }
+            }
+        }
+        if let Value::Array(items) = value {
+            let prefix = schema
+                .get("prefixItems")
+                .and_then(Value::as_array)
+                .map_or(&[][..], Vec::as_slice);
+            for (index, item) in items.iter().enumerate() {
+                let path = format!("{}[{}]", path, index);
+                match (prefix.get(index), schema.get("items")) {
+                    (Some(schema), _) | (None, Some(schema)) => check(root, schema, item, &path)?,
+                    (None, None) => (),
+                }
+            }
+            let len = items.len() as u64;
+            if schema
+                .get("minItems")
+                .and_then(Value::as_u64)
+                .is_some_and(|min| len < min)
+            {
+                return fail("too few items");
+            }
+            if schema
+                .get("maxItems")
+                .and_then(Value::as_u64)
+                .is_some_and(|max| len > max)
+            {
+                return fail("too many items");
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(schema: &Value, value: &Value) -> std::result::Result<(), String> {
+        check(schema, schema, value, "$")
+    }
+
+    fn schemas(commit: &str) -> (Schema, Value, Value) {
+        let schema = Schema::builtin(commit).unwrap();
+        let plain =
+            for_registry(commit, &schema.registry, &schema.root, &schema.short_vec).unwrap();
+        let rpc = rpc(commit, &schema.registry).unwrap();
+        (schema, plain, rpc)
+    }
+
+    #[test]
+    fn vectors_match_their_schemas() {
+        for commit in [b7b4::COMMIT, ce598::COMMIT] {
+            let (schema, plain, rpc_schema) = schemas(commit);
+            let fixtures = Fixtures::read(&Fixtures::path(commit)).unwrap();
+            for vector in &fixtures.vectors {
+                validate(&plain, &vector.json)
+                    .unwrap_or_else(|e| panic!("{} {}: {}", commit, vector.name, e));
+                let rendered = rpc::render(
+                    &schema.registry,
+                    &schema.root,
+                    &schema.short_vec,
+                    &vector.json,
+                )
+                .unwrap();
+                validate(&rpc_schema, &rendered)
+                    .unwrap_or_else(|e| panic!("{} {} rendered: {}", commit, vector.name, e));
+            }
+        }
+    }
+
+    #[test]
+    fn encodings_do_not_match_each_other() {
+        let (schema, plain, rpc_schema) = schemas(ce598::COMMIT);
+        let fixtures = Fixtures::read(&Fixtures::path(ce598::COMMIT)).unwrap();
+        let vector = &fixtures.vectors[0];
+        let rendered = rpc::render(
+            &schema.registry,
+            &schema.root,
+            &schema.short_vec,
+            &vector.json,
+        )
+        .unwrap();
+        // The rendering adds err and turns instruction data into a string.
+        assert!(validate(&plain, &rendered).is_err());
+        assert!(validate(&rpc_schema, &vector.json).is_err());
+    }
+
+    #[test]
+    fn short_vec_length_is_bounded() {
+        let (_, plain, _) = schemas(ce598::COMMIT);
+        let fixtures = Fixtures::read(&Fixtures::path(ce598::COMMIT)).unwrap();
+        let mut value = fixtures.vectors[0].json.clone();
+        assert!(validate(&plain, &value).is_ok());
+        // A ShortU16 length is one to three bytes.
+        value["innerInstructions"][0]["instructions"][0]["accounts"][0] = json!([1, 2, 3, 4]);
+        assert!(validate(&plain, &value).is_err());
+        value["innerInstructions"][0]["instructions"][0]["accounts"] = json!([]);
+        assert!(validate(&plain, &value).is_err());
+    }
+
+    #[test]
+    fn unknown_root() {
+        let schema = Schema::builtin(ce598::COMMIT).unwrap();
+        assert!(matches!(
+            for_registry("id", &schema.registry, "Missing", &[]),
+            Err(Error::MissingContainer(_))
+        ));
+        assert!(matches!(
+            for_registry(
+                "id",
+                &schema.registry,
+                &schema.root,
+                &["TransactionStatusMeta.fee".to_string()]
+            ),
+            Err(Error::NotAVector(_))
+        ));
+    }
+}
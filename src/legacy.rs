@@ -0,0 +1,143 @@
+pub mod b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4;
+pub mod balances;
+pub mod ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb;
+pub mod inner_instructions;
+pub mod limits;
+pub mod validate;
+
+use limits::Limits;
+use thiserror::Error;
+
+/// The versions, by the short commit names used in the rest of the code.
+pub use b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4 as b7b4;
+pub use ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb as ce598;
+
+pub use balances::Balances;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("decode limit exceeded: {0}")]
+    Limit(#[from] limits::Exceeded),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A `TransactionStatusMeta` in whichever legacy layout it was written with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionStatusMeta {
+    B7b4(b7b4::TransactionStatusMeta),
+    Ce598(ce598::TransactionStatusMeta),
+}
+
+impl TransactionStatusMeta {
+    /// The solana-labs/solana commit the layout was copied from.
+    pub fn commit(&self) -> &'static str {
+        match self {
+            TransactionStatusMeta::B7b4(_) => b7b4::COMMIT,
+            TransactionStatusMeta::Ce598(_) => ce598::COMMIT,
+        }
+    }
+
+    pub fn to_bincode(&self) -> bincode::Result<Vec<u8>> {
+        match self {
+            TransactionStatusMeta::B7b4(meta) => b7b4::to_bincode(meta),
+            TransactionStatusMeta::Ce598(meta) => ce598::to_bincode(meta),
+        }
+    }
+}
+
+/// Decodes a blob without knowing its layout, within the default limits.
+pub fn decode(bytes: &[u8]) -> Result<TransactionStatusMeta> {
+    decode_with_limits(bytes, &Limits::default())
+}
+
+/// Decodes a blob without knowing its layout.
+///
+/// Both layouts start with the same fields and ce598 only appends
+/// `inner_instructions`, which it always writes. A blob that ends exactly
+/// after `post_balances` is therefore a b7b4 one; anything else is read as
+/// ce598, whose `default_on_eof` and enum variants accept every b7b4 blob too.
+pub fn decode_with_limits(bytes: &[u8], limits: &Limits) -> Result<TransactionStatusMeta> {
+    // b7b4 encodings are canonical, so re-encoding gives the length read.
+    if let Ok(meta) = b7b4::from_bincode_with_limits(bytes, limits) {
+        if bincode::serialized_size(&meta)? == bytes.len() as u64 {
+            return Ok(TransactionStatusMeta::B7b4(meta));
+        }
+    }
+    ce598::from_bincode_with_limits(bytes, limits).map(TransactionStatusMeta::Ce598)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::CompiledInstruction;
+    use solana_sdk::message::{Message, MessageHeader};
+    use solana_sdk::pubkey::Pubkey;
+
+    /// A message with `keys` account keys and `instructions` empty instructions,
+    /// for the checks against the transaction.
+    pub(super) fn message(keys: usize, instructions: usize) -> Message {
+        Message {
+            header: MessageHeader::default(),
+            account_keys: (0..keys).map(|_| Pubkey::new_unique()).collect(),
+            recent_blockhash: Hash::default(),
+            instructions: vec![
+                CompiledInstruction::new_from_raw_parts(0, vec![], vec![]);
+                instructions
+            ],
+        }
+    }
+
+    fn b7b4() -> b7b4::TransactionStatusMeta {
+        b7b4::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![4000, 1000],
+        }
+    }
+
+    #[test]
+    fn exact_b7b4_blob() {
+        let bytes = b7b4::to_bincode(&b7b4()).unwrap();
+        assert_eq!(decode(&bytes).unwrap(), TransactionStatusMeta::B7b4(b7b4()));
+    }
+
+    #[test]
+    fn ce598_blob() {
+        let meta = ce598::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![4000, 1000],
+            inner_instructions: Some(vec![ce598::InnerInstructions {
+                index: 0,
+                instructions: vec![],
+            }]),
+        };
+        let bytes = ce598::to_bincode(&meta).unwrap();
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded, TransactionStatusMeta::Ce598(meta));
+        assert_eq!(decoded.to_bincode().unwrap(), bytes);
+    }
+
+    #[test]
+    fn b7b4_prefix_with_trailing_bytes() {
+        // A `None` tag after the b7b4 fields is a ce598 meta without inner
+        // instructions, not a b7b4 meta with a stray byte.
+        let mut bytes = b7b4::to_bincode(&b7b4()).unwrap();
+        bytes.push(0);
+        match decode(&bytes).unwrap() {
+            TransactionStatusMeta::Ce598(meta) => assert_eq!(meta.inner_instructions, None),
+            meta => panic!("decoded as {:?}", meta),
+        }
+
+        // Anything else is not an option tag, so neither layout reads it.
+        bytes.pop();
+        bytes.push(7);
+        assert!(matches!(decode(&bytes), Err(Error::Bincode(_))));
+    }
+}
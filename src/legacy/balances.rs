@@ -0,0 +1,149 @@
+use super::{b7b4, ce598, TransactionStatusMeta};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum Error {
+    #[error("{pre} pre balances but {post} post balances")]
+    LengthMismatch { pre: usize, post: usize },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// How a transaction moved lamports, account by account in the order of its
+/// account keys, the fee payer first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Balances {
+    pub fee: u64,
+    /// Post balance minus pre balance of each account.
+    pub deltas: Vec<i128>,
+}
+
+impl Balances {
+    pub fn new(fee: u64, pre_balances: &[u64], post_balances: &[u64]) -> Result<Self> {
+        if pre_balances.len() != post_balances.len() {
+            return Err(Error::LengthMismatch {
+                pre: pre_balances.len(),
+                post: post_balances.len(),
+            });
+        }
+        let deltas = pre_balances
+            .iter()
+            .zip(post_balances)
+            .map(|(&pre, &post)| i128::from(post) - i128::from(pre))
+            .collect();
+        Ok(Self { fee, deltas })
+    }
+
+    /// What the fee payer lost besides the fee, or `None` without accounts.
+    pub fn fee_payer_debit(&self) -> Option<i128> {
+        self.deltas
+            .first()
+            .map(|delta| -delta - i128::from(self.fee))
+    }
+
+    /// The sum of the deltas plus the fee, zero when the transaction only
+    /// moved lamports between its accounts and burnt or collected the fee.
+    /// Failed transactions still charge the fee, so this holds for them too.
+    pub fn imbalance(&self) -> i128 {
+        self.deltas.iter().sum::<i128>() + i128::from(self.fee)
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.imbalance() == 0
+    }
+}
+
+impl b7b4::TransactionStatusMeta {
+    pub fn balances(&self) -> Result<Balances> {
+        Balances::new(self.fee, &self.pre_balances, &self.post_balances)
+    }
+}
+
+impl ce598::TransactionStatusMeta {
+    pub fn balances(&self) -> Result<Balances> {
+        Balances::new(self.fee, &self.pre_balances, &self.post_balances)
+    }
+}
+
+impl TransactionStatusMeta {
+    pub fn balances(&self) -> Result<Balances> {
+        match self {
+            TransactionStatusMeta::B7b4(meta) => meta.balances(),
+            TransactionStatusMeta::Ce598(meta) => meta.balances(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balanced_transfer() {
+        // The fee payer sends 1000 lamports and pays a 5000 lamport fee.
+        let balances = Balances::new(5000, &[10_000, 0, 7], &[4000, 1000, 7]).unwrap();
+        assert_eq!(balances.deltas, vec![-6000, 1000, 0]);
+        assert_eq!(balances.fee_payer_debit(), Some(1000));
+        assert_eq!(balances.imbalance(), 0);
+        assert!(balances.is_consistent());
+    }
+
+    #[test]
+    fn failed_transaction_only_pays_the_fee() {
+        let meta = ce598::TransactionStatusMeta {
+            status: Err(ce598::TransactionError::InstructionError(
+                0,
+                ce598::InstructionError::Custom(1),
+            )),
+            fee: 5000,
+            pre_balances: vec![10_000, 3],
+            post_balances: vec![5000, 3],
+            inner_instructions: None,
+        };
+        let balances = TransactionStatusMeta::Ce598(meta).balances().unwrap();
+        assert_eq!(balances.fee_payer_debit(), Some(0));
+        assert!(balances.is_consistent());
+    }
+
+    #[test]
+    fn imbalanced() {
+        // Lamports appeared out of nowhere.
+        let meta = b7b4::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![5000, 2000],
+        };
+        let balances = TransactionStatusMeta::B7b4(meta).balances().unwrap();
+        assert_eq!(balances.imbalance(), 2000);
+        assert!(!balances.is_consistent());
+    }
+
+    #[test]
+    fn mismatched_lengths() {
+        assert_eq!(
+            Balances::new(0, &[1, 2], &[1]),
+            Err(Error::LengthMismatch { pre: 2, post: 1 })
+        );
+    }
+
+    #[test]
+    fn no_accounts() {
+        let balances = Balances::new(0, &[], &[]).unwrap();
+        assert_eq!(balances.fee_payer_debit(), None);
+        assert!(balances.is_consistent());
+    }
+
+    #[test]
+    fn sums_past_u64() {
+        let max = u64::MAX;
+        let balances = Balances::new(max, &[max, max, 0], &[0, 0, max]).unwrap();
+        let max = i128::from(max);
+        assert_eq!(balances.deltas, vec![-max, -max, max]);
+        assert_eq!(balances.fee_payer_debit(), Some(0));
+        assert_eq!(balances.imbalance(), 0);
+
+        let balances = Balances::new(u64::MAX, &[0, 0], &[u64::MAX, u64::MAX]).unwrap();
+        assert_eq!(balances.imbalance(), 3 * max);
+    }
+}
@@ -0,0 +1,271 @@
+use super::{b7b4, ce598, TransactionStatusMeta};
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum Error {
+    #[error(
+        "inner instructions of instruction {instruction}, \
+         but the transaction has {instructions} instructions"
+    )]
+    InstructionIndex {
+        instruction: u8,
+        instructions: usize,
+    },
+    #[error(
+        "inner instruction {position} of instruction {instruction} refers to account {index}, \
+         but the transaction has {keys} account keys"
+    )]
+    AccountIndex {
+        instruction: u8,
+        position: usize,
+        index: u8,
+        keys: usize,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The inner instructions of a top-level instruction, with their indices
+/// replaced by the account keys they point to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InnerInstructions {
+    /// Index of the top-level instruction in the transaction.
+    pub index: u8,
+    pub instructions: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<Pubkey>,
+    pub data: Vec<u8>,
+}
+
+/// Fails unless `inner` belongs to one of the transaction's `instructions`.
+pub(super) fn check_index(inner: &ce598::InnerInstructions, instructions: usize) -> Result<()> {
+    if usize::from(inner.index) >= instructions {
+        return Err(Error::InstructionIndex {
+            instruction: inner.index,
+            instructions,
+        });
+    }
+    Ok(())
+}
+
+/// The instruction at `position` of the inner instructions of instruction
+/// `index`, with its indices replaced by the `account_keys` they point to.
+pub(super) fn resolve_instruction(
+    index: u8,
+    position: usize,
+    instruction: &ce598::CompiledInstruction,
+    account_keys: &[Pubkey],
+) -> Result<Instruction> {
+    let key = |account: u8| {
+        account_keys
+            .get(usize::from(account))
+            .copied()
+            .ok_or(Error::AccountIndex {
+                instruction: index,
+                position,
+                index: account,
+                keys: account_keys.len(),
+            })
+    };
+    Ok(Instruction {
+        program_id: key(instruction.program_id_index)?,
+        accounts: instruction
+            .accounts
+            .iter()
+            .map(|&account| key(account))
+            .collect::<Result<_>>()?,
+        data: instruction.data.clone(),
+    })
+}
+
+fn resolve(
+    inner: &ce598::InnerInstructions,
+    account_keys: &[Pubkey],
+    instructions: usize,
+) -> Result<InnerInstructions> {
+    check_index(inner, instructions)?;
+    let instructions = inner
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(position, instruction)| {
+            resolve_instruction(inner.index, position, instruction, account_keys)
+        })
+        .collect::<Result<_>>()?;
+    Ok(InnerInstructions {
+        index: inner.index,
+        instructions,
+    })
+}
+
+impl b7b4::TransactionStatusMeta {
+    /// Always `None`: inner instructions were not recorded yet.
+    pub fn resolve_inner_instructions(
+        &self,
+        _account_keys: &[Pubkey],
+        _instructions: usize,
+    ) -> Result<Option<Vec<InnerInstructions>>> {
+        Ok(None)
+    }
+}
+
+impl ce598::TransactionStatusMeta {
+    /// Resolves the inner instructions against the `account_keys` of the
+    /// transaction, checking they belong to one of its `instructions`. `None`
+    /// when the meta does not record inner instructions.
+    pub fn resolve_inner_instructions(
+        &self,
+        account_keys: &[Pubkey],
+        instructions: usize,
+    ) -> Result<Option<Vec<InnerInstructions>>> {
+        self.inner_instructions
+            .as_ref()
+            .map(|inner| {
+                inner
+                    .iter()
+                    .map(|inner| resolve(inner, account_keys, instructions))
+                    .collect()
+            })
+            .transpose()
+    }
+}
+
+impl TransactionStatusMeta {
+    pub fn resolve_inner_instructions(
+        &self,
+        account_keys: &[Pubkey],
+        instructions: usize,
+    ) -> Result<Option<Vec<InnerInstructions>>> {
+        match self {
+            TransactionStatusMeta::B7b4(meta) => {
+                meta.resolve_inner_instructions(account_keys, instructions)
+            }
+            TransactionStatusMeta::Ce598(meta) => {
+                meta.resolve_inner_instructions(account_keys, instructions)
+            }
+        }
+    }
+
+    /// Resolves the inner instructions against a legacy `message`. Versioned
+    /// messages load more keys than they list, so give those to
+    /// `resolve_inner_instructions` instead.
+    pub fn resolve_message_inner_instructions(
+        &self,
+        message: &Message,
+    ) -> Result<Option<Vec<InnerInstructions>>> {
+        self.resolve_inner_instructions(&message.account_keys, message.instructions.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::tests::message;
+
+    fn meta(index: u8, program_id_index: u8, accounts: Vec<u8>) -> TransactionStatusMeta {
+        let instruction = ce598::CompiledInstruction {
+            program_id_index,
+            accounts,
+            data: vec![1, 2, 3],
+        };
+        TransactionStatusMeta::Ce598(ce598::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 0,
+            pre_balances: vec![],
+            post_balances: vec![],
+            inner_instructions: Some(vec![ce598::InnerInstructions {
+                index,
+                instructions: vec![instruction],
+            }]),
+        })
+    }
+
+    #[test]
+    fn resolved_against_the_message() {
+        let message = message(3, 2);
+        let keys = &message.account_keys;
+        assert_eq!(
+            meta(1, 2, vec![0, 1]).resolve_message_inner_instructions(&message),
+            Ok(Some(vec![InnerInstructions {
+                index: 1,
+                instructions: vec![Instruction {
+                    program_id: keys[2],
+                    accounts: vec![keys[0], keys[1]],
+                    data: vec![1, 2, 3],
+                }],
+            }]))
+        );
+    }
+
+    #[test]
+    fn resolved_against_loaded_keys() {
+        // Keys past those of the message, as address lookup tables load them.
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let resolved = meta(0, 3, vec![2])
+            .resolve_inner_instructions(&keys, 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved[0].instructions[0].program_id, keys[3]);
+        assert_eq!(resolved[0].instructions[0].accounts, vec![keys[2]]);
+        assert!(meta(0, 3, vec![2])
+            .resolve_inner_instructions(&keys[..3], 1)
+            .is_err());
+    }
+
+    #[test]
+    fn not_recorded() {
+        let b7b4 = TransactionStatusMeta::B7b4(b7b4::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 0,
+            pre_balances: vec![],
+            post_balances: vec![],
+        });
+        assert_eq!(
+            b7b4.resolve_message_inner_instructions(&message(1, 1)),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn program_id_index_out_of_range() {
+        assert_eq!(
+            meta(0, 3, vec![0]).resolve_message_inner_instructions(&message(3, 1)),
+            Err(Error::AccountIndex {
+                instruction: 0,
+                position: 0,
+                index: 3,
+                keys: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn account_index_out_of_range() {
+        assert_eq!(
+            meta(0, 0, vec![1, 255]).resolve_message_inner_instructions(&message(3, 1)),
+            Err(Error::AccountIndex {
+                instruction: 0,
+                position: 0,
+                index: 255,
+                keys: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn instruction_index_out_of_range() {
+        assert_eq!(
+            meta(2, 0, vec![]).resolve_message_inner_instructions(&message(3, 2)),
+            Err(Error::InstructionIndex {
+                instruction: 2,
+                instructions: 2,
+            })
+        );
+    }
+}
@@ -0,0 +1,714 @@
+use super::{Error, Result};
+use bincode::Options;
+use serde::de::{
+    self, Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+use std::cell::Cell;
+use std::fmt;
+
+/// Bounds on the work and memory spent decoding a single blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Elements in any one sequence. Length prefixes are checked before allocating.
+    pub max_seq_len: usize,
+    /// Size of the whole blob, trailing bytes included.
+    pub max_bytes: usize,
+    /// Nesting of structs and enum values, each counting one level. Sequences,
+    /// tuples and options do not count, as in the generated Go decoders.
+    pub max_depth: usize,
+}
+
+impl Default for Limits {
+    // Well above anything a real transaction produces: at most a few hundred
+    // accounts, and instruction data bounded by the packet size.
+    fn default() -> Self {
+        Self {
+            max_seq_len: 1 << 16,
+            max_bytes: 1 << 24,
+            max_depth: 32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Exceeded {
+    #[error("a sequence of {0} elements is over the limit of {1}")]
+    SeqLen(usize, usize),
+    #[error("{0} bytes are over the limit of {1}")]
+    Bytes(usize, usize),
+    #[error("nesting is over the limit of {0} levels")]
+    Depth(usize),
+}
+
+/// Decodes `bytes` the way `bincode::deserialize` does, within `limits`.
+pub fn deserialize<'de, T: Deserialize<'de>>(bytes: &'de [u8], limits: &Limits) -> Result<T> {
+    if bytes.len() > limits.max_bytes {
+        return Err(Exceeded::Bytes(bytes.len(), limits.max_bytes).into());
+    }
+    let state = State::new(limits);
+    let mut deserializer = bincode::Deserializer::from_slice(bytes, options());
+    T::deserialize(Limited {
+        inner: &mut deserializer,
+        state: &state,
+    })
+    .map_err(|e| match state.exceeded.get() {
+        Some(exceeded) => Error::Limit(exceeded),
+        None => Error::Bincode(e),
+    })
+}
+
+/// The deepest nesting `deserialize` reaches on `bytes`, as `max_depth` counts it.
+pub fn depth<'de, T: Deserialize<'de>>(bytes: &'de [u8], limits: &Limits) -> Result<usize> {
+    let state = State::new(limits);
+    let mut deserializer = bincode::Deserializer::from_slice(bytes, options());
+    T::deserialize(Limited {
+        inner: &mut deserializer,
+        state: &state,
+    })
+    .map_err(|e| match state.exceeded.get() {
+        Some(exceeded) => Error::Limit(exceeded),
+        None => Error::Bincode(e),
+    })?;
+    Ok(state.deepest.get())
+}
+
+/// How many bytes of `bytes` were read when `deserialize` fails on them, or
+/// `None` if it does not. Truncated blobs fail at their end.
+pub fn failure_offset<T: DeserializeOwned>(bytes: &[u8], limits: &Limits) -> Option<usize> {
+    if bytes.len() > limits.max_bytes {
+        return Some(0);
+    }
+    let state = State::new(limits);
+    let mut unread = bytes;
+    let mut deserializer = bincode::Deserializer::with_reader(&mut unread, options());
+    let result = T::deserialize(Limited {
+        inner: &mut deserializer,
+        state: &state,
+    });
+    drop(deserializer);
+    result.err().map(|_| bytes.len() - unread.len())
+}
+
+/// The options bincode::deserialize uses.
+fn options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+}
+
+struct State {
+    limits: Limits,
+    depth: Cell<usize>,
+    deepest: Cell<usize>,
+    /// Set when a limit is hit, since the error itself goes through `de::Error::custom`.
+    exceeded: Cell<Option<Exceeded>>,
+}
+
+impl State {
+    fn new(limits: &Limits) -> Self {
+        Self {
+            limits: *limits,
+            depth: Cell::new(0),
+            deepest: Cell::new(0),
+            exceeded: Cell::new(None),
+        }
+    }
+
+    fn fail<E: de::Error>(&self, exceeded: Exceeded) -> E {
+        self.exceeded.set(Some(exceeded));
+        E::custom(exceeded)
+    }
+
+    fn enter<E: de::Error>(&self) -> std::result::Result<(), E> {
+        let depth = self.depth.get() + 1;
+        if depth > self.limits.max_depth {
+            return Err(self.fail(Exceeded::Depth(self.limits.max_depth)));
+        }
+        self.depth.set(depth);
+        self.deepest.set(self.deepest.get().max(depth));
+        Ok(())
+    }
+
+    fn leave(&self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+
+    fn check_len<E: de::Error>(&self, len: usize) -> std::result::Result<(), E> {
+        if len > self.limits.max_seq_len {
+            return Err(self.fail(Exceeded::SeqLen(len, self.limits.max_seq_len)));
+        }
+        Ok(())
+    }
+}
+
+/// Forwards to `inner`, wrapping every visitor handed to it.
+struct Limited<'s, D> {
+    inner: D,
+    state: &'s State,
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> std::result::Result<V::Value, D::Error> {
+                self.inner.$method($($arg,)* Wrap::new(visitor, self.state))
+            }
+        )*
+    };
+}
+
+macro_rules! nested_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> std::result::Result<V::Value, D::Error> {
+                self.state.enter()?;
+                let value = self.inner.$method($($arg,)* Wrap::new(visitor, self.state));
+                self.state.leave();
+                value
+            }
+        )*
+    };
+}
+
+impl<'de, 's, D: Deserializer<'de>> Deserializer<'de> for Limited<'s, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_map();
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    // Named containers are a level each, the way serde-generate's Go decoders
+    // count them: one per struct, and one per enum value for its variant.
+    nested_deserialize! {
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+    }
+
+    // Only sequences have a length prefix worth checking up front: the tuple
+    // length short_vec asks for is a placeholder.
+    fn deserialize_seq<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, D::Error> {
+        let visitor = Wrap {
+            check_len: true,
+            count: Count::Elements,
+            ..Wrap::new(visitor, self.state)
+        };
+        self.inner.deserialize_seq(visitor)
+    }
+
+    // Tuples are counted as they are read instead, which is what bounds
+    // short_vec, whose first element is its ShortU16 length.
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, D::Error> {
+        let visitor = Wrap {
+            count: Count::AfterFirst,
+            ..Wrap::new(visitor, self.state)
+        };
+        self.inner.deserialize_tuple(len, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+struct Wrap<'s, V> {
+    inner: V,
+    state: &'s State,
+    check_len: bool,
+    count: Count,
+}
+
+impl<'s, V> Wrap<'s, V> {
+    fn new(inner: V, state: &'s State) -> Self {
+        Self {
+            inner,
+            state,
+            check_len: false,
+            count: Count::None,
+        }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            fn $method<E: de::Error>(self, value: $ty) -> std::result::Result<V::Value, E> {
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, 's, V: Visitor<'de>> Visitor<'de> for Wrap<'s, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_str(&str);
+        visit_borrowed_str(&'de str);
+        visit_string(String);
+        visit_bytes(&[u8]);
+        visit_borrowed_bytes(&'de [u8]);
+        visit_byte_buf(Vec<u8>);
+    }
+
+    fn visit_none<E: de::Error>(self) -> std::result::Result<V::Value, E> {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<V::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, d: D) -> std::result::Result<V::Value, D::Error> {
+        self.inner.visit_some(Limited {
+            inner: d,
+            state: self.state,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        d: D,
+    ) -> std::result::Result<V::Value, D::Error> {
+        self.inner.visit_newtype_struct(Limited {
+            inner: d,
+            state: self.state,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> std::result::Result<V::Value, A::Error> {
+        if let (true, Some(len)) = (self.check_len, seq.size_hint()) {
+            self.state.check_len(len)?;
+        }
+        self.inner.visit_seq(LimitedSeq {
+            inner: seq,
+            state: self.state,
+            count: self.count,
+            read: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> std::result::Result<V::Value, A::Error> {
+        if let Some(len) = map.size_hint() {
+            self.state.check_len(len)?;
+        }
+        self.inner.visit_map(LimitedMap {
+            inner: map,
+            state: self.state,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> std::result::Result<V::Value, A::Error> {
+        self.inner.visit_enum(LimitedEnum {
+            inner: data,
+            state: self.state,
+        })
+    }
+}
+
+struct Seed<'s, T> {
+    inner: T,
+    state: &'s State,
+}
+
+impl<'de, 's, T: DeserializeSeed<'de>> DeserializeSeed<'de> for Seed<'s, T> {
+    type Value = T::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> std::result::Result<T::Value, D::Error> {
+        self.inner.deserialize(Limited {
+            inner: d,
+            state: self.state,
+        })
+    }
+}
+
+/// Which elements of a `visit_seq` count against the sequence limit.
+#[derive(Clone, Copy)]
+enum Count {
+    /// Struct fields.
+    None,
+    Elements,
+    /// All but the first.
+    AfterFirst,
+}
+
+/// Also counts elements, for sequences whose length is not known up front.
+struct LimitedSeq<'s, A> {
+    inner: A,
+    state: &'s State,
+    count: Count,
+    read: usize,
+}
+
+impl<'de, 's, A: SeqAccess<'de>> SeqAccess<'de> for LimitedSeq<'s, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> std::result::Result<Option<T::Value>, A::Error> {
+        let element = self.inner.next_element_seed(Seed {
+            inner: seed,
+            state: self.state,
+        })?;
+        if element.is_some() {
+            self.read += 1;
+            match self.count {
+                Count::None => (),
+                Count::Elements => self.state.check_len(self.read)?,
+                Count::AfterFirst => self.state.check_len(self.read - 1)?,
+            }
+        }
+        Ok(element)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct LimitedMap<'s, A> {
+    inner: A,
+    state: &'s State,
+}
+
+impl<'de, 's, A: MapAccess<'de>> MapAccess<'de> for LimitedMap<'s, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> std::result::Result<Option<K::Value>, A::Error> {
+        self.inner.next_key_seed(Seed {
+            inner: seed,
+            state: self.state,
+        })
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> std::result::Result<T::Value, A::Error> {
+        self.inner.next_value_seed(Seed {
+            inner: seed,
+            state: self.state,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct LimitedEnum<'s, A> {
+    inner: A,
+    state: &'s State,
+}
+
+impl<'de, 's, A: EnumAccess<'de>> EnumAccess<'de> for LimitedEnum<'s, A> {
+    type Error = A::Error;
+    type Variant = LimitedEnum<'s, A::Variant>;
+
+    fn variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> std::result::Result<(T::Value, Self::Variant), A::Error> {
+        let (value, variant) = self.inner.variant_seed(Seed {
+            inner: seed,
+            state: self.state,
+        })?;
+        let variant = LimitedEnum {
+            inner: variant,
+            state: self.state,
+        };
+        Ok((value, variant))
+    }
+}
+
+impl<'de, 's, A: VariantAccess<'de>> VariantAccess<'de> for LimitedEnum<'s, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> std::result::Result<(), A::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> std::result::Result<T::Value, A::Error> {
+        self.inner.newtype_variant_seed(Seed {
+            inner: seed,
+            state: self.state,
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> std::result::Result<V::Value, A::Error> {
+        self.inner
+            .tuple_variant(len, Wrap::new(visitor, self.state))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, A::Error> {
+        self.inner
+            .struct_variant(fields, Wrap::new(visitor, self.state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::b7b4;
+    use crate::legacy::ce598::{
+        self, CompiledInstruction, InnerInstructions, TransactionStatusMeta,
+    };
+    use crate::vectors::Fixtures;
+
+    fn meta() -> TransactionStatusMeta {
+        TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10, 20],
+            post_balances: vec![5, 20],
+            inner_instructions: Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![CompiledInstruction {
+                    program_id_index: 1,
+                    accounts: vec![0, 1, 0],
+                    data: vec![],
+                }],
+            }]),
+        }
+    }
+
+    fn exceeded(result: Result<TransactionStatusMeta>) -> Exceeded {
+        match result {
+            Err(Error::Limit(exceeded)) => exceeded,
+            other => panic!("expected a limit error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn within_limits() {
+        let bytes = bincode::serialize(&meta()).unwrap();
+        let limits = Limits::default();
+        assert_eq!(
+            deserialize::<TransactionStatusMeta>(&bytes, &limits).unwrap(),
+            meta()
+        );
+        assert_eq!(
+            failure_offset::<TransactionStatusMeta>(&bytes, &limits),
+            None
+        );
+    }
+
+    #[test]
+    fn huge_length_prefix_fails_before_allocating() {
+        // status Ok, fee, then a pre_balances length of u64::MAX and nothing else.
+        let mut bytes = vec![0; 4];
+        bytes.extend_from_slice(&5000u64.to_le_bytes());
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        let limits = Limits::default();
+        assert_eq!(
+            exceeded(deserialize(&bytes, &limits)),
+            Exceeded::SeqLen(u64::MAX as usize, limits.max_seq_len)
+        );
+        assert_eq!(
+            failure_offset::<TransactionStatusMeta>(&bytes, &limits),
+            Some(bytes.len())
+        );
+    }
+
+    #[test]
+    fn short_vec_over_the_sequence_limit() {
+        let bytes = bincode::serialize(&meta()).unwrap();
+        // Balances have 2 elements, the accounts short_vec 3.
+        let limits = Limits {
+            max_seq_len: 2,
+            ..Limits::default()
+        };
+        assert_eq!(
+            exceeded(deserialize(&bytes, &limits)),
+            Exceeded::SeqLen(3, 2)
+        );
+        // Up to the third account, which is the second to last byte.
+        assert_eq!(
+            failure_offset::<TransactionStatusMeta>(&bytes, &limits),
+            Some(bytes.len() - 1)
+        );
+    }
+
+    #[test]
+    fn struct_fields_do_not_count() {
+        // Five fields, none of the sequences over two elements.
+        let mut meta = meta();
+        meta.inner_instructions.as_mut().unwrap()[0].instructions[0]
+            .accounts
+            .pop();
+        let bytes = bincode::serialize(&meta).unwrap();
+        let limits = Limits {
+            max_seq_len: 2,
+            ..Limits::default()
+        };
+        assert_eq!(
+            deserialize::<TransactionStatusMeta>(&bytes, &limits).unwrap(),
+            meta
+        );
+    }
+
+    #[test]
+    fn blob_over_the_byte_limit() {
+        let bytes = bincode::serialize(&meta()).unwrap();
+        let limits = Limits {
+            max_bytes: bytes.len() - 1,
+            ..Limits::default()
+        };
+        assert_eq!(
+            exceeded(deserialize(&bytes, &limits)),
+            Exceeded::Bytes(bytes.len(), bytes.len() - 1)
+        );
+        assert_eq!(
+            failure_offset::<TransactionStatusMeta>(&bytes, &limits),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn nesting_over_the_depth_limit() {
+        let bytes = bincode::serialize(&meta()).unwrap();
+        // The meta is one level, its status a second.
+        let limits = Limits {
+            max_depth: 1,
+            ..Limits::default()
+        };
+        assert_eq!(exceeded(deserialize(&bytes, &limits)), Exceeded::Depth(1));
+        assert_eq!(
+            failure_offset::<TransactionStatusMeta>(&bytes, &limits),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn only_named_containers_count() {
+        // The meta, an inner instructions and a compiled instruction; neither
+        // the option nor the vectors and short_vecs around them count.
+        let bytes = bincode::serialize(&meta()).unwrap();
+        let limits = Limits::default();
+        assert_eq!(depth::<TransactionStatusMeta>(&bytes, &limits).unwrap(), 3);
+        let limits = Limits {
+            max_depth: 2,
+            ..limits
+        };
+        assert_eq!(exceeded(deserialize(&bytes, &limits)), Exceeded::Depth(2));
+    }
+
+    fn at_the_depth_boundary<T: DeserializeOwned>(commit: &str) {
+        let fixtures = Fixtures::read(&Fixtures::path(commit)).unwrap();
+        for vector in &fixtures.vectors {
+            let bytes = hex::decode(&vector.bincode).unwrap();
+            let limits = Limits {
+                max_depth: vector.depth,
+                ..Limits::default()
+            };
+            assert!(deserialize::<T>(&bytes, &limits).is_ok(), "{}", vector.name);
+            let limits = Limits {
+                max_depth: vector.depth - 1,
+                ..limits
+            };
+            assert!(
+                matches!(
+                    deserialize::<T>(&bytes, &limits),
+                    Err(Error::Limit(Exceeded::Depth(_)))
+                ),
+                "{}",
+                vector.name
+            );
+        }
+    }
+
+    #[test]
+    fn vectors_at_the_depth_boundary() {
+        at_the_depth_boundary::<b7b4::TransactionStatusMeta>(b7b4::COMMIT);
+        at_the_depth_boundary::<TransactionStatusMeta>(ce598::COMMIT);
+    }
+
+    #[test]
+    fn truncated_blob_fails_at_its_end() {
+        let bytes = bincode::serialize(&meta()).unwrap();
+        let truncated = &bytes[..10];
+        let limits = Limits::default();
+        assert!(matches!(
+            deserialize::<TransactionStatusMeta>(truncated, &limits),
+            Err(Error::Bincode(_))
+        ));
+        assert_eq!(
+            failure_offset::<TransactionStatusMeta>(truncated, &limits),
+            Some(truncated.len())
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use super::{b7b4, ce598, inner_instructions, TransactionStatusMeta};
+use serde::{Serialize, Serializer};
+use solana_sdk::message::Message;
+use std::fmt;
+
+/// A rule a meta that decoded may still break, which hints at a mis-detected
+/// layout or a corrupt blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    /// There are as many pre balances as post balances.
+    BalancesLength,
+    /// There is a balance per account key of the message.
+    BalancesAccounts,
+    /// An `InstructionError` points at an instruction of the message.
+    InstructionErrorIndex,
+    /// Inner instructions point at an instruction of the message.
+    InnerInstructionsIndex,
+    /// Inner instructions are grouped once per top-level instruction, in order.
+    InnerInstructionsOrder,
+    /// Inner instructions only refer to account keys of the message.
+    InnerAccountIndex,
+}
+
+impl Rule {
+    /// The name violations are reported under, in text and in JSON.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::BalancesLength => "balances-length",
+            Rule::BalancesAccounts => "balances-accounts",
+            Rule::InstructionErrorIndex => "instruction-error-index",
+            Rule::InnerInstructionsIndex => "inner-instructions-index",
+            Rule::InnerInstructionsOrder => "inner-instructions-order",
+            Rule::InnerAccountIndex => "inner-account-index",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub rule: Rule,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+/// What the rules look at, the same in every layout.
+struct Fields<'a> {
+    pre_balances: usize,
+    post_balances: usize,
+    instruction_error: Option<u8>,
+    inner_instructions: &'a [ce598::InnerInstructions],
+}
+
+impl b7b4::TransactionStatusMeta {
+    /// Checks the meta against the rules, and against the transaction's
+    /// `message` when given. Empty when the meta looks sound.
+    pub fn validate(&self, message: Option<&Message>) -> Vec<Violation> {
+        let instruction_error = match &self.status {
+            Err(b7b4::TransactionError::InstructionError(index, _)) => Some(*index),
+            _ => None,
+        };
+        let fields = Fields {
+            pre_balances: self.pre_balances.len(),
+            post_balances: self.post_balances.len(),
+            instruction_error,
+            inner_instructions: &[],
+        };
+        validate(&fields, message)
+    }
+}
+
+impl ce598::TransactionStatusMeta {
+    /// Checks the meta against the rules, and against the transaction's
+    /// `message` when given. Empty when the meta looks sound.
+    pub fn validate(&self, message: Option<&Message>) -> Vec<Violation> {
+        let instruction_error = match &self.status {
+            Err(ce598::TransactionError::InstructionError(index, _)) => Some(*index),
+            _ => None,
+        };
+        let fields = Fields {
+            pre_balances: self.pre_balances.len(),
+            post_balances: self.post_balances.len(),
+            instruction_error,
+            inner_instructions: self.inner_instructions.as_deref().unwrap_or_default(),
+        };
+        validate(&fields, message)
+    }
+}
+
+impl TransactionStatusMeta {
+    pub fn validate(&self, message: Option<&Message>) -> Vec<Violation> {
+        match self {
+            TransactionStatusMeta::B7b4(meta) => meta.validate(message),
+            TransactionStatusMeta::Ce598(meta) => meta.validate(message),
+        }
+    }
+}
+
+fn validate(fields: &Fields, message: Option<&Message>) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut violate = |rule, message| violations.push(Violation { rule, message });
+
+    if fields.pre_balances != fields.post_balances {
+        violate(
+            Rule::BalancesLength,
+            format!(
+                "{} pre balances but {} post balances",
+                fields.pre_balances, fields.post_balances
+            ),
+        );
+    }
+    // Indices are only checked against the message: the number of balances is
+    // what is being validated, so it cannot stand in for the account keys.
+    if let Some(message) = message {
+        let keys = message.account_keys.len();
+        if fields.pre_balances != keys || fields.post_balances != keys {
+            violate(
+                Rule::BalancesAccounts,
+                format!(
+                    "{} pre and {} post balances for {} account keys",
+                    fields.pre_balances, fields.post_balances, keys
+                ),
+            );
+        }
+        let instructions = message.instructions.len();
+        if let Some(index) = fields.instruction_error {
+            if usize::from(index) >= instructions {
+                violate(
+                    Rule::InstructionErrorIndex,
+                    format!(
+                        "instruction {} failed, but the message has {} instructions",
+                        index, instructions
+                    ),
+                );
+            }
+        }
+        for inner in fields.inner_instructions {
+            if let Err(e) = inner_instructions::check_index(inner, instructions) {
+                violate(Rule::InnerInstructionsIndex, e.to_string());
+            }
+        }
+    }
+
+    for pair in fields.inner_instructions.windows(2) {
+        if pair[0].index >= pair[1].index {
+            violate(
+                Rule::InnerInstructionsOrder,
+                format!(
+                    "inner instructions of instruction {} follow those of instruction {}",
+                    pair[1].index, pair[0].index
+                ),
+            );
+        }
+    }
+    if let Some(message) = message {
+        for inner in fields.inner_instructions {
+            for (position, instruction) in inner.instructions.iter().enumerate() {
+                let resolved = inner_instructions::resolve_instruction(
+                    inner.index,
+                    position,
+                    instruction,
+                    &message.account_keys,
+                );
+                if let Err(e) = resolved {
+                    violate(Rule::InnerAccountIndex, e.to_string());
+                }
+            }
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::tests::message;
+    use crate::vectors::Fixtures;
+
+    fn inner(index: u8, program_id_index: u8, accounts: Vec<u8>) -> ce598::InnerInstructions {
+        ce598::InnerInstructions {
+            index,
+            instructions: vec![ce598::CompiledInstruction {
+                program_id_index,
+                accounts,
+                data: vec![],
+            }],
+        }
+    }
+
+    /// A sound meta for a message with 3 account keys and 2 instructions.
+    fn meta() -> ce598::TransactionStatusMeta {
+        ce598::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10_000, 0, 1],
+            post_balances: vec![5000, 0, 1],
+            inner_instructions: Some(vec![inner(0, 2, vec![0, 1]), inner(1, 2, vec![1])]),
+        }
+    }
+
+    fn rules(meta: &ce598::TransactionStatusMeta, message: Option<&Message>) -> Vec<Rule> {
+        meta.validate(message)
+            .into_iter()
+            .map(|violation| violation.rule)
+            .collect()
+    }
+
+    #[test]
+    fn sound() {
+        assert_eq!(rules(&meta(), Some(&message(3, 2))), vec![]);
+        assert_eq!(rules(&meta(), None), vec![]);
+    }
+
+    #[test]
+    fn balances_length() {
+        let mut meta = meta();
+        meta.post_balances.pop();
+        assert_eq!(rules(&meta, None), vec![Rule::BalancesLength]);
+    }
+
+    #[test]
+    fn balances_accounts() {
+        assert_eq!(
+            rules(&meta(), Some(&message(4, 2))),
+            vec![Rule::BalancesAccounts]
+        );
+    }
+
+    #[test]
+    fn instruction_error_index() {
+        let mut meta = meta();
+        meta.inner_instructions = None;
+        meta.status = Err(ce598::TransactionError::InstructionError(
+            1,
+            ce598::InstructionError::Custom(0),
+        ));
+        assert_eq!(rules(&meta, Some(&message(3, 2))), vec![]);
+        assert_eq!(
+            rules(&meta, Some(&message(3, 1))),
+            vec![Rule::InstructionErrorIndex]
+        );
+    }
+
+    #[test]
+    fn inner_instructions_index() {
+        assert_eq!(
+            rules(&meta(), Some(&message(3, 1))),
+            vec![Rule::InnerInstructionsIndex]
+        );
+    }
+
+    #[test]
+    fn inner_instructions_order() {
+        let mut meta = meta();
+        meta.inner_instructions.as_mut().unwrap().reverse();
+        assert_eq!(rules(&meta, None), vec![Rule::InnerInstructionsOrder]);
+    }
+
+    #[test]
+    fn inner_account_index() {
+        let mut meta = meta();
+        meta.inner_instructions = Some(vec![inner(0, 0, vec![1, 3])]);
+        assert_eq!(
+            rules(&meta, Some(&message(3, 1))),
+            vec![Rule::InnerAccountIndex]
+        );
+        // The same check as resolving them.
+        let message = message(3, 1);
+        let resolved = TransactionStatusMeta::Ce598(meta.clone())
+            .resolve_message_inner_instructions(&message)
+            .unwrap_err();
+        assert_eq!(
+            meta.validate(Some(&message))[0].message,
+            resolved.to_string()
+        );
+        // Without the message the balances are not taken for the account keys.
+        assert_eq!(rules(&meta, None), vec![]);
+    }
+
+    #[test]
+    fn vectors_without_a_message() {
+        let fixtures = Fixtures::read(&Fixtures::path(ce598::COMMIT)).unwrap();
+        for vector in &fixtures.vectors {
+            let bytes = hex::decode(&vector.bincode).unwrap();
+            let meta = ce598::from_bincode(&bytes).unwrap();
+            assert_eq!(meta.validate(None), vec![], "{}", vector.name);
+        }
+    }
+
+    #[test]
+    fn names() {
+        for (rule, name) in [
+            (Rule::BalancesLength, "balances-length"),
+            (Rule::InnerAccountIndex, "inner-account-index"),
+        ] {
+            assert_eq!(rule.to_string(), name);
+            assert_eq!(serde_json::to_value(rule).unwrap(), name);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+pub mod batch;
+pub mod bigtable;
+pub mod blockstore;
+pub mod car;
+pub mod census;
+pub mod generate;
+pub mod golang;
+pub mod json_schema;
+pub mod legacy;
+pub mod meta;
+pub mod proto;
+pub mod rpc;
+pub mod samples;
+pub mod schema;
+pub mod vectors;
@@ -0,0 +1,226 @@
+use crate::legacy::{self, limits, limits::Limits};
+use prost::Message;
+use solana_storage_proto::convert::generated;
+use solana_storage_proto::StoredTransactionStatusMeta;
+use solana_transaction_status::TransactionStatusMeta;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("empty blob")]
+    Empty,
+    #[error("invalid protobuf meta: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+    #[error("invalid transaction error in protobuf meta: {0}")]
+    ProtobufStatus(bincode::Error),
+    #[error("invalid bincode meta: {0}")]
+    Bincode(#[from] legacy::Error),
+    #[error("neither protobuf ({protobuf}) nor bincode ({bincode})")]
+    Undecodable {
+        protobuf: prost::DecodeError,
+        bincode: legacy::Error,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// How a meta was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Encoding {
+    Protobuf,
+    Bincode,
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Protobuf => write!(f, "protobuf"),
+            Encoding::Bincode => write!(f, "bincode"),
+        }
+    }
+}
+
+/// Which layout of its encoding a meta was written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Version {
+    /// The layouts of solana-storage-proto.
+    Current,
+    /// A legacy bincode layout, by commit.
+    Legacy(&'static str),
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Version::Current => write!(f, "current"),
+            Version::Legacy(commit) => write!(f, "{}", commit),
+        }
+    }
+}
+
+/// A meta in the current type, along with how it was written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoded {
+    pub meta: TransactionStatusMeta,
+    pub encoding: Encoding,
+    pub version: Version,
+}
+
+/// Decodes a meta the way the blockstore reads it: as protobuf first, then as
+/// bincode `StoredTransactionStatusMeta`.
+///
+/// The legacy layouts are prefixes of the stored one and their enums only
+/// ever gained variants at the end, so it reads them all with the same
+/// meaning. They are only used to tell the version: a blob that is exactly
+/// one of them was written with it.
+pub fn decode(bytes: &[u8], limits: &Limits) -> Result<Decoded> {
+    if bytes.is_empty() {
+        return Err(Error::Empty);
+    }
+    let protobuf = match decode_protobuf(bytes) {
+        Err(Error::Protobuf(e)) => e,
+        decoded => return decoded,
+    };
+    let bincode = match decode_bincode(bytes, limits) {
+        Err(Error::Bincode(e)) => e,
+        decoded => return decoded,
+    };
+    Err(Error::Undecodable { protobuf, bincode })
+}
+
+/// Decodes a protobuf meta.
+///
+/// Protobuf skips the fields it does not know and defaults the missing ones,
+/// so short blobs of another encoding often decode. A meta always has the
+/// balance of its fee payer, so one without balances is rejected.
+pub fn decode_protobuf(bytes: &[u8]) -> Result<Decoded> {
+    let meta = generated::TransactionStatusMeta::decode(bytes)?;
+    if meta.pre_balances.is_empty() && meta.post_balances.is_empty() {
+        return Err(prost::DecodeError::new("no balances").into());
+    }
+    Ok(Decoded {
+        meta: meta.try_into().map_err(Error::ProtobufStatus)?,
+        encoding: Encoding::Protobuf,
+        version: Version::Current,
+    })
+}
+
+/// Decodes a bincode meta, telling its legacy layout if it has one.
+pub fn decode_bincode(bytes: &[u8], limits: &Limits) -> Result<Decoded> {
+    let meta = limits::deserialize::<StoredTransactionStatusMeta>(bytes, limits)?;
+    Ok(Decoded {
+        meta: meta.into(),
+        encoding: Encoding::Bincode,
+        version: legacy_version(bytes, limits),
+    })
+}
+
+fn legacy_version(bytes: &[u8], limits: &Limits) -> Version {
+    match legacy::decode_with_limits(bytes, limits) {
+        Ok(meta) if meta.to_bincode().map(|b| b.len()).ok() == Some(bytes.len()) => {
+            Version::Legacy(meta.commit())
+        }
+        _ => Version::Current,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::{b7b4, ce598};
+
+    fn current() -> TransactionStatusMeta {
+        TransactionStatusMeta {
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![4000, 1000],
+            log_messages: Some(vec!["Program log: hi".to_string()]),
+            // What protobuf decodes absent lists to.
+            pre_token_balances: Some(vec![]),
+            post_token_balances: Some(vec![]),
+            rewards: Some(vec![]),
+            ..TransactionStatusMeta::default()
+        }
+    }
+
+    fn b7b4() -> b7b4::TransactionStatusMeta {
+        b7b4::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![4000, 1000],
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Decoded> {
+        super::decode(bytes, &Limits::default())
+    }
+
+    #[test]
+    fn protobuf() {
+        let bytes = generated::TransactionStatusMeta::from(current()).encode_to_vec();
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(
+            (decoded.encoding, decoded.version),
+            (Encoding::Protobuf, Version::Current)
+        );
+        assert_eq!(decoded.meta, current());
+    }
+
+    #[test]
+    fn current_bincode() {
+        let stored = StoredTransactionStatusMeta::try_from(current()).unwrap();
+        let bytes = bincode::serialize(&stored).unwrap();
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(
+            (decoded.encoding, decoded.version),
+            (Encoding::Bincode, Version::Current)
+        );
+        assert_eq!(decoded.meta, current());
+    }
+
+    #[test]
+    fn legacy_bincode() {
+        let bytes = b7b4::to_bincode(&b7b4()).unwrap();
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(
+            (decoded.encoding, decoded.version),
+            (Encoding::Bincode, Version::Legacy(b7b4::COMMIT))
+        );
+        assert_eq!(decoded.meta.pre_balances, b7b4().pre_balances);
+
+        let ce598 = ce598::TransactionStatusMeta {
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![10_000, 0],
+            post_balances: vec![4000, 1000],
+            inner_instructions: Some(vec![]),
+        };
+        let bytes = ce598::to_bincode(&ce598).unwrap();
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(
+            (decoded.encoding, decoded.version),
+            (Encoding::Bincode, Version::Legacy(ce598::COMMIT))
+        );
+        assert_eq!(decoded.meta.inner_instructions, Some(vec![]));
+    }
+
+    #[test]
+    fn bincode_is_not_protobuf() {
+        // Bincode metas start with a small little-endian enum variant, which
+        // is field number 0 to protobuf and never valid.
+        let bytes = b7b4::to_bincode(&b7b4()).unwrap();
+        assert!(matches!(decode_protobuf(&bytes), Err(Error::Protobuf(_))));
+    }
+
+    #[test]
+    fn empty_and_short_blobs() {
+        assert!(matches!(decode(&[]), Err(Error::Empty)));
+        // Only a fee, to protobuf; too short for bincode.
+        assert!(matches!(
+            decode(&[0x10, 0x05]),
+            Err(Error::Undecodable { .. })
+        ));
+    }
+}
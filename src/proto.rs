@@ -0,0 +1,305 @@
+use inflector::cases::snakecase::to_snake_case;
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
+use std::collections::BTreeSet;
+use std::io::Write;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0} has no protobuf representation: {1:?}")]
+    Unsupported(String, Format),
+    #[error(
+        "the registry has a container named {0}, which clashes with the synthetic message for ()"
+    )]
+    NameClash(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Writes a proto3 schema with one message per container of `registry`.
+///
+/// Structs become messages with fields numbered in declaration order. Enums
+/// become messages holding a single `oneof`, where the field number of each
+/// variant is its bincode variant index plus one. `Option<Vec<T>>` follows
+/// Solana's own `confirmed_block.proto`: a repeated field plus a
+/// `<name>_none` flag numbered after all the regular fields.
+pub fn output(out: &mut dyn Write, package: &str, registry: &Registry) -> Result<()> {
+    let mut emitter = Emitter {
+        package,
+        needs_unit: false,
+        nested: BTreeSet::new(),
+        body: Vec::new(),
+    };
+    for (name, container) in registry {
+        emitter.output_container(name, container)?;
+    }
+    if emitter.needs_unit && registry.contains_key("Unit") {
+        return Err(Error::NameClash("Unit".to_string()));
+    }
+
+    writeln!(out, "syntax = \"proto3\";")?;
+    writeln!(out)?;
+    writeln!(out, "package {};", package)?;
+    writeln!(out)?;
+    if emitter.needs_unit {
+        writeln!(out, "message Unit {{}}")?;
+        writeln!(out)?;
+    }
+    out.write_all(&emitter.body)?;
+    Ok(())
+}
+
+struct Emitter<'a> {
+    package: &'a str,
+    needs_unit: bool,
+    /// Names of the messages nested in the enum being written, which shadow top-level names.
+    nested: BTreeSet<String>,
+    body: Vec<u8>,
+}
+
+impl<'a> Emitter<'a> {
+    fn output_container(&mut self, name: &str, container: &ContainerFormat) -> Result<()> {
+        match container {
+            ContainerFormat::UnitStruct => {
+                writeln!(self.body, "message {} {{}}", name)?;
+            }
+            ContainerFormat::NewTypeStruct(format) => {
+                let fields = vec![Named {
+                    name: "value".to_string(),
+                    value: format.as_ref().clone(),
+                }];
+                self.output_message(name, &fields, 0)?;
+            }
+            ContainerFormat::TupleStruct(formats) => {
+                self.output_message(name, &tuple_fields(formats), 0)?;
+            }
+            ContainerFormat::Struct(fields) => {
+                self.output_message(name, fields, 0)?;
+            }
+            ContainerFormat::Enum(variants) => {
+                writeln!(self.body, "message {} {{", name)?;
+                self.nested = variants
+                    .values()
+                    .filter(|variant| match &variant.value {
+                        VariantFormat::Unit => false,
+                        VariantFormat::NewType(format) => self.singular_type(format).is_none(),
+                        _ => true,
+                    })
+                    .map(|variant| variant.name.clone())
+                    .collect();
+                let mut oneof = Vec::new();
+                for (index, variant) in variants {
+                    let field = to_snake_case(&variant.name);
+                    let number = index + 1;
+                    let type_name = match &variant.value {
+                        VariantFormat::Unit => self.unit_type(),
+                        VariantFormat::NewType(format) => match self.singular_type(format) {
+                            Some(type_name) => type_name,
+                            None => {
+                                let fields = vec![Named {
+                                    name: "value".to_string(),
+                                    value: format.as_ref().clone(),
+                                }];
+                                self.output_message(&variant.name, &fields, 1)?;
+                                variant.name.clone()
+                            }
+                        },
+                        VariantFormat::Tuple(formats) => {
+                            self.output_message(&variant.name, &tuple_fields(formats), 1)?;
+                            variant.name.clone()
+                        }
+                        VariantFormat::Struct(fields) => {
+                            self.output_message(&variant.name, fields, 1)?;
+                            variant.name.clone()
+                        }
+                        VariantFormat::Variable(_) => {
+                            unreachable!("registry contains unresolved variants")
+                        }
+                    };
+                    oneof.push(format!("{} {} = {};", type_name, field, number));
+                }
+                writeln!(self.body, "  oneof {} {{", to_snake_case(name))?;
+                for line in oneof {
+                    writeln!(self.body, "    {}", line)?;
+                }
+                writeln!(self.body, "  }}")?;
+                writeln!(self.body, "}}")?;
+                self.nested.clear();
+            }
+        }
+        writeln!(self.body)?;
+        Ok(())
+    }
+
+    fn output_message(&mut self, name: &str, fields: &[Named<Format>], depth: usize) -> Result<()> {
+        let indent = "  ".repeat(depth);
+        writeln!(self.body, "{}message {} {{", indent, name)?;
+        let mut none_flags = Vec::new();
+        for (position, field) in fields.iter().enumerate() {
+            let field_name = to_snake_case(&field.name);
+            let number = position + 1;
+            let (label, type_name) = match &field.value {
+                Format::Option(inner) => match inner.as_ref() {
+                    Format::Seq(content) if !matches!(content.as_ref(), Format::U8) => {
+                        none_flags.push(format!("{}_none", field_name));
+                        ("repeated ", self.element_type(&field.name, content)?)
+                    }
+                    format => ("optional ", self.field_type(&field.name, format)?),
+                },
+                Format::Seq(content) if !matches!(content.as_ref(), Format::U8) => {
+                    ("repeated ", self.element_type(&field.name, content)?)
+                }
+                format => ("", self.field_type(&field.name, format)?),
+            };
+            writeln!(
+                self.body,
+                "{}  {}{} {} = {};",
+                indent, label, type_name, field_name, number
+            )?;
+        }
+        for (offset, flag) in none_flags.iter().enumerate() {
+            writeln!(
+                self.body,
+                "{}  bool {} = {};",
+                indent,
+                flag,
+                fields.len() + offset + 1
+            )?;
+        }
+        writeln!(self.body, "{}}}", indent)?;
+        Ok(())
+    }
+
+    fn field_type(&mut self, field: &str, format: &Format) -> Result<String> {
+        self.singular_type(format)
+            .ok_or_else(|| Error::Unsupported(field.to_string(), format.clone()))
+    }
+
+    fn element_type(&mut self, field: &str, format: &Format) -> Result<String> {
+        match format {
+            // Protobuf has no way to nest repeated or optional values inside a repeated field.
+            Format::Seq(_) | Format::Option(_) => {
+                Err(Error::Unsupported(field.to_string(), format.clone()))
+            }
+            _ => self.field_type(field, format),
+        }
+    }
+
+    /// The protobuf type of a value that is neither repeated nor optional, if any.
+    fn singular_type(&mut self, format: &Format) -> Option<String> {
+        let type_name = match format {
+            Format::TypeName(name) => self.type_ref(name),
+            Format::Unit => self.unit_type(),
+            Format::Bool => "bool".to_string(),
+            Format::I8 | Format::I16 | Format::I32 => "int32".to_string(),
+            Format::I64 => "int64".to_string(),
+            Format::U8 | Format::U16 | Format::U32 => "uint32".to_string(),
+            Format::U64 => "uint64".to_string(),
+            Format::F32 => "float".to_string(),
+            Format::F64 => "double".to_string(),
+            Format::Char | Format::Str => "string".to_string(),
+            // 128-bit integers are stored as their 16 little-endian bytes.
+            Format::Bytes | Format::I128 | Format::U128 => "bytes".to_string(),
+            Format::Seq(content) if matches!(content.as_ref(), Format::U8) => "bytes".to_string(),
+            Format::Tuple(_) | Format::TupleArray { .. } if is_byte_tuple(format) => {
+                "bytes".to_string()
+            }
+            _ => return None,
+        };
+        Some(type_name)
+    }
+
+    fn unit_type(&mut self) -> String {
+        self.needs_unit = true;
+        self.type_ref("Unit")
+    }
+
+    fn type_ref(&self, name: &str) -> String {
+        if self.nested.contains(name) {
+            format!(".{}.{}", self.package, name)
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+fn tuple_fields(formats: &[Format]) -> Vec<Named<Format>> {
+    formats
+        .iter()
+        .enumerate()
+        .map(|(position, format)| Named {
+            name: format!("field_{}", position),
+            value: format.clone(),
+        })
+        .collect()
+}
+
+/// Whether `format` is a (possibly nested) tuple of `u8`, a fixed-size byte
+/// array like the ones of keys and hashes.
+fn is_byte_tuple(format: &Format) -> bool {
+    match format {
+        Format::U8 => true,
+        Format::Tuple(formats) => formats.iter().all(is_byte_tuple),
+        Format::TupleArray { content, .. } => is_byte_tuple(content),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(containers: Vec<(&str, ContainerFormat)>) -> Registry {
+        containers
+            .into_iter()
+            .map(|(name, container)| (name.to_string(), container))
+            .collect()
+    }
+
+    #[test]
+    fn unit_message_is_emitted_once() {
+        let registry = registry(vec![(
+            "Meta",
+            ContainerFormat::Struct(vec![Named {
+                name: "status".to_string(),
+                value: Format::Unit,
+            }]),
+        )]);
+        let mut out = Vec::new();
+        output(&mut out, "meta", &registry).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("message Unit {}").count(), 1);
+    }
+
+    #[test]
+    fn unit_container_clashes_with_unit_message() {
+        let registry = registry(vec![
+            (
+                "Meta",
+                ContainerFormat::Struct(vec![Named {
+                    name: "status".to_string(),
+                    value: Format::Unit,
+                }]),
+            ),
+            ("Unit", ContainerFormat::UnitStruct),
+        ]);
+        let err = output(&mut Vec::new(), "meta", &registry).unwrap_err();
+        assert!(matches!(err, Error::NameClash(name) if name == "Unit"));
+    }
+
+    #[test]
+    fn unit_container_alone_is_fine() {
+        let registry = registry(vec![("Unit", ContainerFormat::UnitStruct)]);
+        let mut out = Vec::new();
+        output(&mut out, "meta", &registry).unwrap();
+        assert_eq!(
+            String::from_utf8(out)
+                .unwrap()
+                .matches("message Unit")
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use serde_json::{Map, Value};
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("container {0} is not in the registry")]
+    MissingContainer(String),
+    #[error("{0} does not match {1:?}")]
+    Mismatch(Value, Format),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Turns the serde_json encoding of a `root` value into its RPC rendering, as
+/// described by `json_schema::rpc`: the root gets an `err` field mirroring the
+/// error of `status`, `short_vec` fields (given as `Container.field`) lose
+/// their length, and compiled instructions carry base58-encoded data.
+pub fn render(
+    registry: &Registry,
+    root: &str,
+    short_vec: &[String],
+    value: &Value,
+) -> Result<Value> {
+    let renderer = Renderer {
+        registry,
+        short_vec,
+    };
+    let mut rendered = renderer.format(&Format::TypeName(root.to_string()), value)?;
+    if let Value::Object(fields) = &mut rendered {
+        let err = match fields.get("status") {
+            Some(Value::Object(status)) => status.get("Err").cloned().unwrap_or(Value::Null),
+            _ => Value::Null,
+        };
+        fields.insert("err".to_string(), err);
+    }
+    Ok(rendered)
+}
+
+struct Renderer<'a> {
+    registry: &'a Registry,
+    short_vec: &'a [String],
+}
+
+impl<'a> Renderer<'a> {
+    fn format(&self, format: &Format, value: &Value) -> Result<Value> {
+        let mismatch = || Error::Mismatch(value.clone(), format.clone());
+        let rendered = match (format, value) {
+            (Format::TypeName(name), value) => self.container(name, value)?,
+            (Format::Option(_), Value::Null) => Value::Null,
+            (Format::Option(inner), value) => self.format(inner, value)?,
+            (Format::Seq(inner), Value::Array(values))
+            | (Format::TupleArray { content: inner, .. }, Value::Array(values)) => Value::Array(
+                values
+                    .iter()
+                    .map(|value| self.format(inner, value))
+                    .collect::<Result<_>>()?,
+            ),
+            (Format::Tuple(formats), value) => self.tuple(formats, value)?,
+            (Format::Map { value: inner, .. }, Value::Object(map)) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| Ok((key.clone(), self.format(inner, value)?)))
+                    .collect::<Result<_>>()?,
+            ),
+            (Format::Seq(_), _) | (Format::TupleArray { .. }, _) | (Format::Map { .. }, _) => {
+                return Err(mismatch())
+            }
+            (Format::Variable(_), _) => unreachable!("registry contains unresolved formats"),
+            (_, value) => value.clone(),
+        };
+        Ok(rendered)
+    }
+
+    fn container(&self, name: &str, value: &Value) -> Result<Value> {
+        let container = self
+            .registry
+            .get(name)
+            .ok_or_else(|| Error::MissingContainer(name.to_string()))?;
+        let format = Format::TypeName(name.to_string());
+        let rendered = match container {
+            ContainerFormat::UnitStruct => value.clone(),
+            ContainerFormat::NewTypeStruct(inner) => self.format(inner, value)?,
+            ContainerFormat::TupleStruct(formats) => self.tuple(formats, value)?,
+            ContainerFormat::Struct(fields) => self.fields(name, fields, value)?,
+            ContainerFormat::Enum(variants) => {
+                // serde_json writes unit variants as strings and the others as single-key objects.
+                let (variant_name, content) = match value {
+                    Value::String(_) => return Ok(value.clone()),
+                    Value::Object(map) if map.len() == 1 => map.iter().next().unwrap(),
+                    _ => return Err(Error::Mismatch(value.clone(), format)),
+                };
+                let variant = variants
+                    .values()
+                    .find(|variant| &variant.name == variant_name)
+                    .ok_or_else(|| Error::Mismatch(value.clone(), format.clone()))?;
+                let content = match &variant.value {
+                    VariantFormat::Unit => content.clone(),
+                    VariantFormat::NewType(inner) => self.format(inner, content)?,
+                    VariantFormat::Tuple(formats) => self.tuple(formats, content)?,
+                    VariantFormat::Struct(fields) => self.fields(name, fields, content)?,
+                    VariantFormat::Variable(_) => {
+                        unreachable!("registry contains unresolved variants")
+                    }
+                };
+                let mut map = Map::new();
+                map.insert(variant_name.clone(), content);
+                Value::Object(map)
+            }
+        };
+        Ok(rendered)
+    }
+
+    fn fields(&self, name: &str, fields: &[Named<Format>], value: &Value) -> Result<Value> {
+        let mut rendered = Map::new();
+        for field in fields {
+            let mut content = value.get(&field.name).unwrap_or(&Value::Null).clone();
+            if self.short_vec.contains(&format!("{}.{}", name, field.name)) {
+                // serde_json writes a short_vec as its length followed by the items.
+                match content.as_array_mut() {
+                    Some(items) if !items.is_empty() => {
+                        items.remove(0);
+                    }
+                    _ => return Err(Error::Mismatch(content, field.value.clone())),
+                }
+            }
+            let mut content = self.format(&field.value, &content)?;
+            if name == "CompiledInstruction" && field.name == "data" {
+                let bytes: Vec<u8> = serde_json::from_value(content.clone())
+                    .map_err(|_| Error::Mismatch(content.clone(), field.value.clone()))?;
+                content = Value::String(bs58::encode(bytes).into_string());
+            }
+            rendered.insert(field.name.clone(), content);
+        }
+        Ok(Value::Object(rendered))
+    }
+
+    fn tuple(&self, formats: &[Format], value: &Value) -> Result<Value> {
+        match value {
+            Value::Array(values) if values.len() == formats.len() => Ok(Value::Array(
+                formats
+                    .iter()
+                    .zip(values)
+                    .map(|(format, value)| self.format(format, value))
+                    .collect::<Result<_>>()?,
+            )),
+            _ => Err(Error::Mismatch(
+                value.clone(),
+                Format::Tuple(formats.to_vec()),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::ce598;
+    use crate::schema::Schema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use serde_reflection::{Samples, Tracer, TracerConfig};
+
+    fn meta(status: ce598::Result<()>, data: Vec<u8>) -> Value {
+        serde_json::to_value(ce598::TransactionStatusMeta {
+            status,
+            fee: 5000,
+            pre_balances: vec![1, 2],
+            post_balances: vec![1, 2],
+            inner_instructions: Some(vec![ce598::InnerInstructions {
+                index: 0,
+                instructions: vec![ce598::CompiledInstruction {
+                    program_id_index: 1,
+                    accounts: vec![0, 1],
+                    data,
+                }],
+            }]),
+        })
+        .unwrap()
+    }
+
+    fn render_ce598(value: &Value) -> Result<Value> {
+        let schema = Schema::builtin(ce598::COMMIT).unwrap();
+        render(&schema.registry, &schema.root, &schema.short_vec, value)
+    }
+
+    #[test]
+    fn compiled_instruction_data_is_base58() {
+        let rendered = render_ce598(&meta(Ok(()), vec![1, 2, 3])).unwrap();
+        let instruction = &rendered["innerInstructions"][0]["instructions"][0];
+        assert_eq!(instruction["data"], json!("Ldp"));
+        // The short_vec length is gone from both.
+        assert_eq!(instruction["accounts"], json!([0, 1]));
+
+        let rendered = render_ce598(&meta(Ok(()), vec![])).unwrap();
+        assert_eq!(
+            rendered["innerInstructions"][0]["instructions"][0]["data"],
+            json!("")
+        );
+    }
+
+    #[test]
+    fn only_compiled_instruction_data_is_base58() {
+        #[derive(Serialize, Deserialize)]
+        struct Other {
+            data: Vec<u8>,
+        }
+        let mut tracer = Tracer::new(TracerConfig::default());
+        tracer.trace_type::<Other>(&Samples::new()).unwrap();
+        let registry = tracer.registry().unwrap();
+        let value = serde_json::to_value(Other {
+            data: vec![1, 2, 3],
+        })
+        .unwrap();
+        assert_eq!(
+            render(&registry, "Other", &[], &value).unwrap(),
+            json!({ "data": [1, 2, 3], "err": null })
+        );
+    }
+
+    #[test]
+    fn err_mirrors_the_status() {
+        let rendered = render_ce598(&meta(Ok(()), vec![])).unwrap();
+        assert_eq!(rendered["err"], Value::Null);
+        assert_eq!(rendered["status"], json!({ "Ok": null }));
+
+        let status = Err(ce598::TransactionError::InstructionError(
+            0,
+            ce598::InstructionError::Custom(1),
+        ));
+        let rendered = render_ce598(&meta(status, vec![])).unwrap();
+        let err = json!({ "InstructionError": [0, { "Custom": 1 }] });
+        assert_eq!(rendered["err"], err);
+        assert_eq!(rendered["status"], json!({ "Err": err }));
+    }
+
+    #[test]
+    fn mismatches() {
+        let mut value = meta(Ok(()), vec![1]);
+        value["innerInstructions"][0]["instructions"][0]["data"] = json!([[1], "a"]);
+        assert!(matches!(render_ce598(&value), Err(Error::Mismatch(..))));
+        let mut value = meta(Ok(()), vec![1]);
+        value["innerInstructions"][0]["instructions"][0]["accounts"] = json!([]);
+        assert!(matches!(render_ce598(&value), Err(Error::Mismatch(..))));
+        let mut value = meta(Ok(()), vec![1]);
+        value["status"] = json!({ "Unknown": null });
+        assert!(matches!(render_ce598(&value), Err(Error::Mismatch(..))));
+        let schema = Schema::builtin(ce598::COMMIT).unwrap();
+        assert!(matches!(
+            render(&schema.registry, "Missing", &[], &value),
+            Err(Error::MissingContainer(_))
+        ));
+    }
+}
@@ -0,0 +1,386 @@
+use serde::de::{DeserializeOwned, IntoDeserializer};
+use serde::{Deserialize, Serialize};
+use serde_reflection::{
+    ContainerFormat, Format, Named, Registry, Samples, Tracer, TracerConfig, Value, VariantFormat,
+};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("tracing error: {0}")]
+    Trace(#[from] serde_reflection::Error),
+    #[error("container {0} is not in the registry")]
+    MissingContainer(String),
+    #[error("container {0} contains itself")]
+    Recursive(String),
+    #[error("{0} is not a container")]
+    NotAContainer(String),
+    #[error("field {0} is declared as a vector but has a fixed-size tuple format")]
+    SampleDependent(String),
+    #[error("field {0} is not in the registry")]
+    MissingField(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Builds values of a type covering every variant of every enum it contains,
+/// at every nesting level, from the type's traced format.
+///
+/// serde-reflection only explores all the variants of the enum it is asked to
+/// trace, so nested enums have to be traced first with `trace`, innermost first.
+pub struct Sampler {
+    tracer: Tracer,
+    samples: Samples,
+    /// Containers whose values are taken as given rather than enumerated.
+    fixed: BTreeMap<String, Value>,
+    /// Formats of fields whose traced format depends on the sample, as `Container.field`.
+    declared: BTreeMap<String, Format>,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sampler {
+    pub fn new() -> Self {
+        Self {
+            tracer: Tracer::new(TracerConfig::default().record_samples_for_structs(true)),
+            samples: Samples::new(),
+            fixed: BTreeMap::new(),
+            declared: BTreeMap::new(),
+        }
+    }
+
+    /// Uses `value` for every occurrence of its type, for structs whose
+    /// `Deserialize` impl cannot be traced, e.g. because of `short_vec`.
+    pub fn fix<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        match self.tracer.trace_value(&mut self.samples, value)? {
+            (Format::TypeName(name), value) => {
+                self.fixed.insert(name, value);
+                Ok(())
+            }
+            (format, _) => Err(Error::NotAContainer(format!("{:?}", format))),
+        }
+    }
+
+    /// Replaces the traced format of `field`, given as `Container.field`, in the
+    /// registry. Needed for the fields of fixed containers that serialize as
+    /// tuples, e.g. `short_vec` ones, whose traced length is the sample's.
+    pub fn declare(&mut self, field: &str, format: Format) {
+        self.declared.insert(field.to_string(), format);
+    }
+
+    /// Finds all the variants of `T`, if it is an enum.
+    pub fn trace<T: DeserializeOwned>(&mut self) -> Result<()> {
+        self.tracer.trace_type::<T>(&self.samples)?;
+        Ok(())
+    }
+
+    /// Values of `T`, each named after the variants it selects, along with the
+    /// traced registry in which declared fields have been replaced.
+    pub fn enumerate<T: for<'de> Deserialize<'de>>(
+        mut self,
+    ) -> Result<(Registry, Vec<(String, T)>)> {
+        let (format, _) = self.tracer.trace_type::<T>(&self.samples)?;
+        let mut registry = self.tracer.registry()?;
+        let enumerator = Enumerator {
+            registry: &registry,
+            fixed: &self.fixed,
+        };
+        let mut values = Vec::new();
+        for (names, value) in enumerator.format(&format, &mut Vec::new())? {
+            let value = T::deserialize(value.into_deserializer())?;
+            values.push((names.join("/"), value));
+        }
+        for (field, format) in &self.declared {
+            *find_field_mut(&mut registry, field)? = format.clone();
+        }
+        check(&registry, self.declared.keys())?;
+        Ok((registry, values))
+    }
+}
+
+/// Fails if a field declared as a vector still contains a fixed-size tuple,
+/// the shape a sample of a sequence is traced as. Tuples elsewhere, like the
+/// arrays of a `Pubkey`, are fixed-size in the type too.
+fn check<'a>(registry: &Registry, declared: impl IntoIterator<Item = &'a String>) -> Result<()> {
+    for field in declared {
+        if contains_tuple(find_field(registry, field)?) {
+            return Err(Error::SampleDependent(field.clone()));
+        }
+    }
+    Ok(())
+}
+
+fn find_field<'a>(registry: &'a Registry, field: &str) -> Result<&'a Format> {
+    let missing = || Error::MissingField(field.to_string());
+    let (container, name) = field.split_once('.').ok_or_else(missing)?;
+    match registry.get(container) {
+        Some(ContainerFormat::Struct(fields)) => fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| &field.value)
+            .ok_or_else(missing),
+        _ => Err(missing()),
+    }
+}
+
+fn find_field_mut<'a>(registry: &'a mut Registry, field: &str) -> Result<&'a mut Format> {
+    let missing = || Error::MissingField(field.to_string());
+    let (container, name) = field.split_once('.').ok_or_else(missing)?;
+    match registry.get_mut(container) {
+        Some(ContainerFormat::Struct(fields)) => fields
+            .iter_mut()
+            .find(|field| field.name == name)
+            .map(|field| &mut field.value)
+            .ok_or_else(missing),
+        _ => Err(missing()),
+    }
+}
+
+fn contains_tuple(format: &Format) -> bool {
+    match format {
+        Format::Tuple(_) | Format::TupleArray { .. } => true,
+        Format::Option(inner) | Format::Seq(inner) => contains_tuple(inner),
+        Format::Map { key, value } => contains_tuple(key) || contains_tuple(value),
+        _ => false,
+    }
+}
+
+/// Alternative values of a format: the first one picks the first variant of
+/// every enum, each of the others differs from it at one place.
+type Alternatives = Vec<(Vec<String>, Value)>;
+
+struct Enumerator<'a> {
+    registry: &'a Registry,
+    fixed: &'a BTreeMap<String, Value>,
+}
+
+impl<'a> Enumerator<'a> {
+    fn format(&self, format: &Format, stack: &mut Vec<String>) -> Result<Alternatives> {
+        let alternatives = match format {
+            Format::TypeName(name) => self.container(name, stack)?,
+            Format::Option(inner) => {
+                let mut alternatives: Alternatives = self
+                    .format(inner, stack)?
+                    .into_iter()
+                    .map(|(names, value)| (names, Value::Option(Some(Box::new(value)))))
+                    .collect();
+                alternatives.push((vec!["None".to_string()], Value::Option(None)));
+                alternatives
+            }
+            Format::Seq(inner) => self
+                .format(inner, stack)?
+                .into_iter()
+                .map(|(names, value)| (names, Value::Seq(vec![value])))
+                .collect(),
+            Format::Map { key, value } => {
+                let key = self.format(key, stack)?.swap_remove(0).1;
+                self.format(value, stack)?
+                    .into_iter()
+                    .map(|(names, value)| (names, Value::Seq(vec![key.clone(), value])))
+                    .collect()
+            }
+            Format::Tuple(formats) => {
+                let fields = formats
+                    .iter()
+                    .map(|format| Ok((None, self.format(format, stack)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                combine(fields)
+            }
+            Format::TupleArray { content, size } => {
+                let fields = (0..*size)
+                    .map(|_| Ok((None, self.format(content, stack)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                combine(fields)
+            }
+            Format::Variable(_) => unreachable!("registry contains unresolved formats"),
+            format => vec![(Vec::new(), primitive(format))],
+        };
+        Ok(alternatives)
+    }
+
+    fn container(&self, name: &str, stack: &mut Vec<String>) -> Result<Alternatives> {
+        if let Some(value) = self.fixed.get(name) {
+            return Ok(vec![(Vec::new(), value.clone())]);
+        }
+        if stack.iter().any(|container| container == name) {
+            return Err(Error::Recursive(name.to_string()));
+        }
+        let container = self
+            .registry
+            .get(name)
+            .ok_or_else(|| Error::MissingContainer(name.to_string()))?;
+        stack.push(name.to_string());
+        let alternatives = match container {
+            ContainerFormat::UnitStruct => vec![(Vec::new(), Value::Unit)],
+            ContainerFormat::NewTypeStruct(format) => self.format(format, stack)?,
+            ContainerFormat::TupleStruct(formats) => {
+                self.format(&Format::Tuple(formats.clone()), stack)?
+            }
+            ContainerFormat::Struct(fields) => self.fields(fields, stack)?,
+            ContainerFormat::Enum(variants) => {
+                let mut alternatives = Vec::new();
+                for (index, variant) in variants {
+                    let payloads = match &variant.value {
+                        VariantFormat::Unit => vec![(Vec::new(), Value::Unit)],
+                        VariantFormat::NewType(format) => self.format(format, stack)?,
+                        VariantFormat::Tuple(formats) => {
+                            self.format(&Format::Tuple(formats.clone()), stack)?
+                        }
+                        VariantFormat::Struct(fields) => self.fields(fields, stack)?,
+                        VariantFormat::Variable(_) => {
+                            unreachable!("registry contains unresolved variants")
+                        }
+                    };
+                    for (names, payload) in payloads {
+                        let names = std::iter::once(variant.name.clone()).chain(names).collect();
+                        alternatives.push((names, Value::Variant(*index, Box::new(payload))));
+                    }
+                }
+                alternatives
+            }
+        };
+        stack.pop();
+        Ok(alternatives)
+    }
+
+    fn fields(&self, fields: &[Named<Format>], stack: &mut Vec<String>) -> Result<Alternatives> {
+        let fields = fields
+            .iter()
+            .map(|field| Ok((Some(field.name.as_str()), self.format(&field.value, stack)?)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(combine(fields))
+    }
+}
+
+/// The alternatives of a struct or tuple: all fields at their first
+/// alternative, then each other alternative of each field in turn. Those of
+/// named fields after the first are prefixed with the field name.
+fn combine(fields: Vec<(Option<&str>, Alternatives)>) -> Alternatives {
+    let first: Vec<Value> = fields
+        .iter()
+        .map(|(_, alternatives)| alternatives[0].1.clone())
+        .collect();
+    let first_names = fields
+        .iter()
+        .flat_map(|(_, alternatives)| alternatives[0].0.clone())
+        .collect();
+    let mut combined = vec![(first_names, Value::Seq(first.clone()))];
+    for (position, (name, alternatives)) in fields.into_iter().enumerate() {
+        for (names, value) in alternatives.into_iter().skip(1) {
+            let mut values = first.clone();
+            values[position] = value;
+            let names = match name {
+                Some(name) if position > 0 => {
+                    std::iter::once(name.to_string()).chain(names).collect()
+                }
+                _ => names,
+            };
+            combined.push((names, Value::Seq(values)));
+        }
+    }
+    combined
+}
+
+/// The largest value of each primitive, so that samples exercise every byte
+/// of its encoding.
+fn primitive(format: &Format) -> Value {
+    match format {
+        Format::Unit => Value::Unit,
+        Format::Bool => Value::Bool(true),
+        Format::I8 => Value::I8(i8::MAX),
+        Format::I16 => Value::I16(i16::MAX),
+        Format::I32 => Value::I32(i32::MAX),
+        Format::I64 => Value::I64(i64::MAX),
+        Format::I128 => Value::I128(i128::MAX),
+        Format::U8 => Value::U8(u8::MAX),
+        Format::U16 => Value::U16(u16::MAX),
+        Format::U32 => Value::U32(u32::MAX),
+        Format::U64 => Value::U64(u64::MAX),
+        Format::U128 => Value::U128(u128::MAX),
+        Format::F32 => Value::F32(f32::MAX),
+        Format::F64 => Value::F64(f64::MAX),
+        Format::Char => Value::Char('z'),
+        Format::Str => Value::Str("sample".to_string()),
+        Format::Bytes => Value::Bytes(vec![u8::MAX]),
+        _ => unreachable!("not a primitive: {:?}", format),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuple() -> Format {
+        Format::Tuple(vec![Format::U8, Format::U8])
+    }
+
+    fn pubkey() -> Format {
+        Format::TupleArray {
+            content: Box::new(Format::U8),
+            size: 32,
+        }
+    }
+
+    fn field(name: &str, value: Format) -> Named<Format> {
+        Named {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    /// An instruction whose `accounts` are a vector and `data` a traced
+    /// sample, along with a fixed-size key.
+    fn registry(data: Format) -> Registry {
+        let instruction = ContainerFormat::Struct(vec![
+            field("program_id", pubkey()),
+            field("accounts", Format::Seq(Box::new(Format::U8))),
+            field("data", data),
+        ]);
+        Registry::from([("Instruction".to_string(), instruction)])
+    }
+
+    fn declared(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    #[test]
+    fn declared_vectors_pass() {
+        let registry = registry(Format::Seq(Box::new(Format::U8)));
+        check(
+            &registry,
+            &declared(&["Instruction.accounts", "Instruction.data"]),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn tuples_of_undeclared_fields_pass() {
+        // The key is a real array, and `data` was not declared a vector.
+        check(&registry(tuple()), &declared(&["Instruction.accounts"])).unwrap();
+    }
+
+    #[test]
+    fn declared_vector_traced_as_a_tuple() {
+        let registry = registry(Format::Option(Box::new(tuple())));
+        match check(&registry, &declared(&["Instruction.data"])) {
+            Err(Error::SampleDependent(field)) => assert_eq!(field, "Instruction.data"),
+            other => panic!("expected a sample-dependent format, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn declared_field_missing() {
+        let registry = registry(tuple());
+        for field in ["Instruction.signers", "Message.data", "data"] {
+            assert!(matches!(
+                check(&registry, &declared(&[field])),
+                Err(Error::MissingField(_))
+            ));
+        }
+    }
+}
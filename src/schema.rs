@@ -0,0 +1,136 @@
+pub mod compat;
+pub mod diff;
+
+use serde::{Deserialize, Serialize};
+use serde_reflection::Registry;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error on {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("invalid schema file {0}: {1}")]
+    Yaml(PathBuf, serde_yaml::Error),
+    #[error("no schema found for {0}")]
+    NotFound(String),
+    #[error("{0} matches several schemas")]
+    Ambiguous(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Directory, relative to the repository root, where the schema files are checked in.
+pub const DIR: &str = "schemas";
+
+/// The checked-in schemas, built into the crate so that they can be used
+/// outside of the repository.
+const BUILTIN: &[(&str, &str)] = &[
+    (
+        "b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4",
+        include_str!("../schemas/b7b4aa5d4d34ebf3fd338a64f4f2a5257b047bb4.yaml"),
+    ),
+    (
+        "ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb",
+        include_str!("../schemas/ce598c5c98e7384c104fe7f5121e32c2c5a2d2eb.yaml"),
+    ),
+];
+
+/// A traced registry together with where its types come from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Schema {
+    /// The solana-labs/solana commit the types were copied from.
+    pub commit: String,
+    /// The container stored in the blockstore, which every other container hangs off.
+    pub root: String,
+    /// Fields declared with `deserialize_with = "default_on_eof"`, as `Container.field`.
+    #[serde(default)]
+    pub default_on_eof: Vec<String>,
+    /// Fields declared with `with = "short_vec"`, as `Container.field`.
+    #[serde(default)]
+    pub short_vec: Vec<String>,
+    pub sources: Vec<Source>,
+    pub registry: Registry,
+}
+
+/// The upstream definition of a container.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub container: String,
+    pub file: String,
+    pub first_line: u32,
+    pub last_line: u32,
+}
+
+impl Source {
+    pub fn new(container: &str, file: &str, first_line: u32, last_line: u32) -> Self {
+        Self {
+            container: container.to_string(),
+            file: file.to_string(),
+            first_line,
+            last_line,
+        }
+    }
+
+    pub fn url(&self, commit: &str) -> String {
+        let lines = if self.first_line == self.last_line {
+            format!("L{}", self.first_line)
+        } else {
+            format!("L{}-L{}", self.first_line, self.last_line)
+        };
+        format!(
+            "https://github.com/solana-labs/solana/blob/{}/{}#{}",
+            commit, self.file, lines
+        )
+    }
+}
+
+impl Schema {
+    /// The conventional location of the schema for `commit`.
+    pub fn path(commit: &str) -> PathBuf {
+        Path::new(DIR).join(format!("{}.yaml", commit))
+    }
+
+    /// Reads the schema at `path_or_commit`, or else the checked-in schema of the
+    /// commit it names, which may be abbreviated.
+    pub fn load(path_or_commit: &str) -> Result<Self> {
+        let path = Path::new(path_or_commit);
+        if path.is_file() {
+            return Self::read(path);
+        }
+        let entries = std::fs::read_dir(DIR).map_err(|e| Error::Io(PathBuf::from(DIR), e))?;
+        let mut matches = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| Error::Io(PathBuf::from(DIR), e))?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with(path_or_commit) && file_name.ends_with(".yaml") {
+                matches.push(entry.path());
+            }
+        }
+        match matches.as_slice() {
+            [] => Err(Error::NotFound(path_or_commit.to_string())),
+            [path] => Self::read(path),
+            _ => Err(Error::Ambiguous(path_or_commit.to_string())),
+        }
+    }
+
+    /// The checked-in schema of `commit`, as built into the crate.
+    pub fn builtin(commit: &str) -> Result<Self> {
+        let (_, contents) = BUILTIN
+            .iter()
+            .find(|(builtin, _)| *builtin == commit)
+            .ok_or_else(|| Error::NotFound(commit.to_string()))?;
+        serde_yaml::from_str(contents).map_err(|e| Error::Yaml(Self::path(commit), e))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))?;
+        serde_yaml::from_str(&contents).map_err(|e| Error::Yaml(path.to_path_buf(), e))
+    }
+
+    /// The contents of its file at `Self::path`, as the generators write it.
+    pub fn contents(&self) -> serde_yaml::Result<String> {
+        serde_yaml::to_string(self)
+    }
+}
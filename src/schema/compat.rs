@@ -0,0 +1,494 @@
+use super::diff::describe;
+use super::Schema;
+use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Older bytes still decode, but the change is worth knowing about.
+    Note,
+    /// Some older bytes fail to decode, or decode to something else.
+    Incompatible,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Where the finding is, e.g. `TransactionStatusMeta.status` or `InstructionError::Custom`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Note => "note",
+            Severity::Incompatible => "incompatible",
+        };
+        write!(f, "{}: {}: {}", severity, self.path, self.message)
+    }
+}
+
+/// Whether bytes written with the layout of `old` are read back by the layout of `new`.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub old: String,
+    pub new: String,
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    pub fn is_compatible(&self) -> bool {
+        self.findings
+            .iter()
+            .all(|finding| finding.severity < Severity::Incompatible)
+    }
+}
+
+/// Checks every pair of `schemas`, from the oldest to the newest, in the order given.
+pub fn check_all(schemas: &[Schema]) -> Vec<Report> {
+    let mut reports = Vec::new();
+    for (position, old) in schemas.iter().enumerate() {
+        for new in &schemas[position + 1..] {
+            reports.push(check(old, new));
+        }
+    }
+    reports
+}
+
+/// Walks both layouts from their roots the way bincode reads them: by position,
+/// ignoring names. Trailing root fields are allowed in both directions, since
+/// bincode ignores trailing bytes and `default_on_eof` fills in missing ones.
+pub fn check(old: &Schema, new: &Schema) -> Report {
+    let mut checker = Checker {
+        old,
+        new,
+        visited: BTreeSet::new(),
+        findings: Vec::new(),
+    };
+    checker.container(&old.root, &new.root, true);
+    Report {
+        old: old.commit.clone(),
+        new: new.commit.clone(),
+        findings: checker.findings,
+    }
+}
+
+struct Checker<'a> {
+    old: &'a Schema,
+    new: &'a Schema,
+    visited: BTreeSet<(String, String)>,
+    findings: Vec<Finding>,
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, severity: Severity, path: &str, message: String) {
+        self.findings.push(Finding {
+            severity,
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn container(&mut self, old_name: &str, new_name: &str, is_root: bool) {
+        if !self
+            .visited
+            .insert((old_name.to_string(), new_name.to_string()))
+        {
+            return;
+        }
+        let (old, new) = match (
+            self.old.registry.get(old_name),
+            self.new.registry.get(new_name),
+        ) {
+            (Some(old), Some(new)) => (old, new),
+            _ => {
+                let message = format!("{} or {} is missing from its registry", old_name, new_name);
+                self.report(Severity::Incompatible, old_name, message);
+                return;
+            }
+        };
+        match (old, new) {
+            (ContainerFormat::UnitStruct, ContainerFormat::UnitStruct) => (),
+            (ContainerFormat::NewTypeStruct(old), ContainerFormat::NewTypeStruct(new)) => {
+                self.format(old_name, old, new)
+            }
+            (ContainerFormat::TupleStruct(old), ContainerFormat::TupleStruct(new))
+                if old.len() == new.len() =>
+            {
+                for (position, (old, new)) in old.iter().zip(new).enumerate() {
+                    self.format(&format!("{}.{}", old_name, position), old, new);
+                }
+            }
+            (ContainerFormat::Struct(old), ContainerFormat::Struct(new)) => {
+                self.fields(old_name, new_name, old, new, is_root)
+            }
+            (ContainerFormat::Enum(old), ContainerFormat::Enum(new)) => {
+                self.variants(old_name, old, new)
+            }
+            _ => self.report(
+                Severity::Incompatible,
+                old_name,
+                format!("the container changed shape and is now {}", new_name),
+            ),
+        }
+    }
+
+    fn fields(
+        &mut self,
+        container: &str,
+        new_container: &str,
+        old: &[Named<Format>],
+        new: &[Named<Format>],
+        is_root: bool,
+    ) {
+        for position in 0..old.len().max(new.len()) {
+            match (old.get(position), new.get(position)) {
+                (Some(old_field), Some(new_field)) => {
+                    let path = format!("{}.{}", container, old_field.name);
+                    if old_field.name != new_field.name {
+                        if new.iter().any(|field| field.name == old_field.name) {
+                            self.report(
+                                Severity::Incompatible,
+                                &path,
+                                format!("moved, {} is now read in its place", new_field.name),
+                            );
+                            continue;
+                        }
+                        self.report(
+                            Severity::Note,
+                            &path,
+                            format!("renamed to {}", new_field.name),
+                        );
+                    }
+                    self.format(&path, &old_field.value, &new_field.value);
+                    let new_path = format!("{}.{}", new_container, new_field.name);
+                    if self.old.short_vec.contains(&path) != self.new.short_vec.contains(&new_path)
+                    {
+                        self.report(
+                            Severity::Incompatible,
+                            &path,
+                            "length switched between a u64 and a short_vec ShortU16".to_string(),
+                        );
+                    }
+                }
+                (Some(old_field), None) => {
+                    let path = format!("{}.{}", container, old_field.name);
+                    if is_root {
+                        self.report(
+                            Severity::Note,
+                            &path,
+                            "removed, its bytes are left unread".to_string(),
+                        );
+                    } else {
+                        self.report(
+                            Severity::Incompatible,
+                            &path,
+                            "removed, its bytes would be read as the next value".to_string(),
+                        );
+                    }
+                }
+                (None, Some(new_field)) => {
+                    let path = format!("{}.{}", container, new_field.name);
+                    if is_root && self.new.default_on_eof.contains(&path) {
+                        self.report(
+                            Severity::Note,
+                            &path,
+                            "absent from older bytes, defaulted on EOF".to_string(),
+                        );
+                    } else {
+                        self.report(
+                            Severity::Incompatible,
+                            &path,
+                            format!(
+                                "added as {} without a default, older bytes end before it",
+                                describe(&new_field.value)
+                            ),
+                        );
+                    }
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+    }
+
+    fn variants(
+        &mut self,
+        container: &str,
+        old: &BTreeMap<u32, Named<VariantFormat>>,
+        new: &BTreeMap<u32, Named<VariantFormat>>,
+    ) {
+        for (index, old_variant) in old {
+            let path = format!("{}::{}", container, old_variant.name);
+            let new_variant = match new.get(index) {
+                Some(new_variant) => new_variant,
+                None => {
+                    self.report(
+                        Severity::Incompatible,
+                        &path,
+                        format!("tag {} is unknown to the newer layout", index),
+                    );
+                    continue;
+                }
+            };
+            if old_variant.name != new_variant.name {
+                let moved_to = new
+                    .iter()
+                    .find(|(_, variant)| variant.name == old_variant.name)
+                    .map(|(index, _)| *index);
+                if let Some(moved_to) = moved_to {
+                    self.report(
+                        Severity::Incompatible,
+                        &path,
+                        format!(
+                            "tag {} now means {}, the variant moved to tag {}",
+                            index, new_variant.name, moved_to
+                        ),
+                    );
+                    continue;
+                }
+                self.report(
+                    Severity::Note,
+                    &path,
+                    format!(
+                        "renamed to {} with the same tag {}",
+                        new_variant.name, index
+                    ),
+                );
+            }
+            match (&old_variant.value, &new_variant.value) {
+                (VariantFormat::Unit, VariantFormat::Unit) => (),
+                (VariantFormat::NewType(old), VariantFormat::NewType(new)) => {
+                    self.format(&path, old, new)
+                }
+                (VariantFormat::Tuple(old), VariantFormat::Tuple(new))
+                    if old.len() == new.len() =>
+                {
+                    for (position, (old, new)) in old.iter().zip(new).enumerate() {
+                        self.format(&format!("{}.{}", path, position), old, new);
+                    }
+                }
+                (VariantFormat::Struct(old), VariantFormat::Struct(new)) => {
+                    self.fields(&path, &path, old, new, false)
+                }
+                _ => self.report(
+                    Severity::Incompatible,
+                    &path,
+                    "the variant payload changed shape".to_string(),
+                ),
+            }
+        }
+    }
+
+    fn format(&mut self, path: &str, old: &Format, new: &Format) {
+        match (old, new) {
+            (Format::TypeName(old), Format::TypeName(new)) => self.container(old, new, false),
+            (Format::Option(old), Format::Option(new)) | (Format::Seq(old), Format::Seq(new)) => {
+                self.format(path, old, new)
+            }
+            (
+                Format::Map {
+                    key: old_key,
+                    value: old_value,
+                },
+                Format::Map {
+                    key: new_key,
+                    value: new_value,
+                },
+            ) => {
+                self.format(path, old_key, new_key);
+                self.format(path, old_value, new_value);
+            }
+            (Format::Tuple(old), Format::Tuple(new)) if old.len() == new.len() => {
+                for (old, new) in old.iter().zip(new) {
+                    self.format(path, old, new);
+                }
+            }
+            (
+                Format::TupleArray {
+                    content: old,
+                    size: old_size,
+                },
+                Format::TupleArray {
+                    content: new,
+                    size: new_size,
+                },
+            ) if old_size == new_size => self.format(path, old, new),
+            (old, new) if old == new => (),
+            (old, new) => self.report(
+                Severity::Incompatible,
+                path,
+                format!("{} cannot be read as {}", describe(old), describe(new)),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_reflection::Registry;
+
+    fn field(name: &str, value: Format) -> Named<Format> {
+        Named {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    fn unit_enum(names: &[&str]) -> ContainerFormat {
+        let variants = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let variant = Named {
+                    name: name.to_string(),
+                    value: VariantFormat::Unit,
+                };
+                (index as u32, variant)
+            })
+            .collect();
+        ContainerFormat::Enum(variants)
+    }
+
+    fn schema(commit: &str, containers: Vec<(&str, ContainerFormat)>) -> Schema {
+        let registry: Registry = containers
+            .into_iter()
+            .map(|(name, container)| (name.to_string(), container))
+            .collect();
+        Schema {
+            commit: commit.to_string(),
+            root: "Meta".to_string(),
+            default_on_eof: vec![],
+            short_vec: vec![],
+            sources: vec![],
+            registry,
+        }
+    }
+
+    fn meta(fields: Vec<Named<Format>>) -> (&'static str, ContainerFormat) {
+        ("Meta", ContainerFormat::Struct(fields))
+    }
+
+    fn findings(report: &Report) -> Vec<String> {
+        report.findings.iter().map(ToString::to_string).collect()
+    }
+
+    fn with_error(names: &[&str]) -> Schema {
+        schema(
+            &names.join(""),
+            vec![
+                meta(vec![field("status", Format::TypeName("Error".to_string()))]),
+                ("Error", unit_enum(names)),
+            ],
+        )
+    }
+
+    #[test]
+    fn appended_variant_is_compatible() {
+        let report = check(&with_error(&["A"]), &with_error(&["A", "B"]));
+        assert!(report.is_compatible());
+        assert_eq!(findings(&report), Vec::<String>::new());
+    }
+
+    #[test]
+    fn renamed_variant_with_the_same_tag_is_a_note() {
+        let report = check(&with_error(&["A", "B"]), &with_error(&["A", "C"]));
+        assert!(report.is_compatible());
+        assert_eq!(
+            findings(&report),
+            vec!["note: Error::B: renamed to C with the same tag 1"]
+        );
+    }
+
+    #[test]
+    fn moved_variant_is_incompatible() {
+        let report = check(&with_error(&["A", "B"]), &with_error(&["A", "C", "B"]));
+        assert!(!report.is_compatible());
+        assert_eq!(
+            findings(&report),
+            vec!["incompatible: Error::B: tag 1 now means C, the variant moved to tag 2"]
+        );
+    }
+
+    #[test]
+    fn removed_variant_is_incompatible() {
+        let report = check(&with_error(&["A", "B"]), &with_error(&["A"]));
+        assert_eq!(
+            findings(&report),
+            vec!["incompatible: Error::B: tag 1 is unknown to the newer layout"]
+        );
+    }
+
+    #[test]
+    fn trailing_root_field_with_default_on_eof_is_a_note() {
+        let old = schema("old", vec![meta(vec![field("fee", Format::U64)])]);
+        let mut new = schema(
+            "new",
+            vec![meta(vec![
+                field("fee", Format::U64),
+                field("inner", Format::Option(Box::new(Format::U8))),
+            ])],
+        );
+        new.default_on_eof.push("Meta.inner".to_string());
+        let report = check(&old, &new);
+        assert!(report.is_compatible());
+        assert_eq!(
+            findings(&report),
+            vec!["note: Meta.inner: absent from older bytes, defaulted on EOF"]
+        );
+    }
+
+    #[test]
+    fn trailing_root_field_without_default_on_eof_is_incompatible() {
+        let old = schema("old", vec![meta(vec![field("fee", Format::U64)])]);
+        let new = schema(
+            "new",
+            vec![meta(vec![
+                field("fee", Format::U64),
+                field("inner", Format::Option(Box::new(Format::U8))),
+            ])],
+        );
+        let report = check(&old, &new);
+        assert!(!report.is_compatible());
+        assert_eq!(
+            findings(&report),
+            vec!["incompatible: Meta.inner: added as Option<u8> without a default, older bytes end before it"]
+        );
+    }
+
+    #[test]
+    fn trailing_root_field_removed_is_a_note() {
+        let old = schema(
+            "old",
+            vec![meta(vec![
+                field("fee", Format::U64),
+                field("extra", Format::U8),
+            ])],
+        );
+        let new = schema("new", vec![meta(vec![field("fee", Format::U64)])]);
+        let report = check(&old, &new);
+        assert!(report.is_compatible());
+        assert_eq!(
+            findings(&report),
+            vec!["note: Meta.extra: removed, its bytes are left unread"]
+        );
+    }
+
+    #[test]
+    fn short_vec_switch_is_incompatible() {
+        let data = || vec![meta(vec![field("data", Format::Seq(Box::new(Format::U8)))])];
+        let old = schema("old", data());
+        let mut new = schema("new", data());
+        assert!(check(&old, &new).is_compatible());
+
+        new.short_vec.push("Meta.data".to_string());
+        let report = check(&old, &new);
+        assert_eq!(
+            findings(&report),
+            vec!["incompatible: Meta.data: length switched between a u64 and a short_vec ShortU16"]
+        );
+        assert_eq!(findings(&check(&new, &old)), findings(&report));
+    }
+}
@@ -0,0 +1,650 @@
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A difference between two registries, seen from the older one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    ContainerAdded {
+        container: String,
+    },
+    ContainerRemoved {
+        container: String,
+    },
+    ContainerKindChanged {
+        container: String,
+        old: ContainerFormat,
+        new: ContainerFormat,
+    },
+    FieldAdded {
+        container: String,
+        position: usize,
+        name: String,
+        format: Format,
+        /// Whether the field comes after every field of the older layout.
+        trailing: bool,
+    },
+    FieldRemoved {
+        container: String,
+        position: usize,
+        name: String,
+    },
+    FieldRenamed {
+        container: String,
+        position: usize,
+        old: String,
+        new: String,
+    },
+    FieldMoved {
+        container: String,
+        name: String,
+        old: usize,
+        new: usize,
+    },
+    FieldTypeChanged {
+        container: String,
+        name: String,
+        old: Format,
+        new: Format,
+    },
+    VariantAdded {
+        container: String,
+        index: u32,
+        name: String,
+        /// Whether the variant comes after every variant of the older layout,
+        /// so that no existing bincode tag moves.
+        appended: bool,
+    },
+    VariantRemoved {
+        container: String,
+        index: u32,
+        name: String,
+    },
+    VariantRenamed {
+        container: String,
+        index: u32,
+        old: String,
+        new: String,
+    },
+    /// The bincode tag of a variant changed, typically because of an insertion before it.
+    VariantIndexChanged {
+        container: String,
+        name: String,
+        old: u32,
+        new: u32,
+    },
+    VariantPayloadChanged {
+        container: String,
+        name: String,
+        old: VariantFormat,
+        new: VariantFormat,
+    },
+}
+
+impl Change {
+    pub fn container(&self) -> &str {
+        match self {
+            Change::ContainerAdded { container }
+            | Change::ContainerRemoved { container }
+            | Change::ContainerKindChanged { container, .. }
+            | Change::FieldAdded { container, .. }
+            | Change::FieldRemoved { container, .. }
+            | Change::FieldRenamed { container, .. }
+            | Change::FieldMoved { container, .. }
+            | Change::FieldTypeChanged { container, .. }
+            | Change::VariantAdded { container, .. }
+            | Change::VariantRemoved { container, .. }
+            | Change::VariantRenamed { container, .. }
+            | Change::VariantIndexChanged { container, .. }
+            | Change::VariantPayloadChanged { container, .. } => container,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::ContainerAdded { container } => write!(f, "+ {}", container),
+            Change::ContainerRemoved { container } => write!(f, "- {}", container),
+            Change::ContainerKindChanged {
+                container,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}: {} became {}",
+                container,
+                container_kind(old),
+                container_kind(new)
+            ),
+            Change::FieldAdded {
+                container,
+                position,
+                name,
+                format,
+                trailing,
+            } => write!(
+                f,
+                "+ {}.{}: {} at position {}{}",
+                container,
+                name,
+                describe(format),
+                position,
+                if *trailing { " (trailing)" } else { "" }
+            ),
+            Change::FieldRemoved {
+                container,
+                position,
+                name,
+            } => write!(f, "- {}.{} at position {}", container, name, position),
+            Change::FieldRenamed {
+                container,
+                position,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}.{} renamed to {} at position {}",
+                container, old, new, position
+            ),
+            Change::FieldMoved {
+                container,
+                name,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}.{} moved from position {} to {}",
+                container, name, old, new
+            ),
+            Change::FieldTypeChanged {
+                container,
+                name,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}.{}: {} became {}",
+                container,
+                name,
+                describe(old),
+                describe(new)
+            ),
+            Change::VariantAdded {
+                container,
+                index,
+                name,
+                appended,
+            } => write!(
+                f,
+                "+ {}::{} with tag {}{}",
+                container,
+                name,
+                index,
+                if *appended {
+                    " (appended)"
+                } else {
+                    " (inserted)"
+                }
+            ),
+            Change::VariantRemoved {
+                container,
+                index,
+                name,
+            } => write!(f, "- {}::{} with tag {}", container, name, index),
+            Change::VariantRenamed {
+                container,
+                index,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}::{} renamed to {} with tag {}",
+                container, old, new, index
+            ),
+            Change::VariantIndexChanged {
+                container,
+                name,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}::{} tag shifted from {} to {}",
+                container, name, old, new
+            ),
+            Change::VariantPayloadChanged {
+                container,
+                name,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}::{}: {} became {}",
+                container,
+                name,
+                describe_variant(old),
+                describe_variant(new)
+            ),
+        }
+    }
+}
+
+/// Lists the changes needed to go from `old` to `new`, container by container.
+pub fn diff(old: &Registry, new: &Registry) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (name, old_container) in old {
+        match new.get(name) {
+            None => changes.push(Change::ContainerRemoved {
+                container: name.clone(),
+            }),
+            Some(new_container) => diff_container(name, old_container, new_container, &mut changes),
+        }
+    }
+    for name in new.keys() {
+        if !old.contains_key(name) {
+            changes.push(Change::ContainerAdded {
+                container: name.clone(),
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.container().cmp(b.container()));
+    changes
+}
+
+fn diff_container(
+    name: &str,
+    old: &ContainerFormat,
+    new: &ContainerFormat,
+    changes: &mut Vec<Change>,
+) {
+    match (old, new) {
+        (ContainerFormat::Struct(old), ContainerFormat::Struct(new)) => {
+            diff_fields(name, old, new, changes)
+        }
+        (ContainerFormat::Enum(old), ContainerFormat::Enum(new)) => {
+            diff_variants(name, old, new, changes)
+        }
+        (old, new) if old == new => (),
+        (old, new) => changes.push(Change::ContainerKindChanged {
+            container: name.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+fn diff_fields(
+    container: &str,
+    old: &[Named<Format>],
+    new: &[Named<Format>],
+    changes: &mut Vec<Change>,
+) {
+    let position =
+        |fields: &[Named<Format>], name: &str| fields.iter().position(|field| field.name == name);
+    let mut removed = BTreeMap::new();
+    for (old_position, field) in old.iter().enumerate() {
+        match position(new, &field.name) {
+            None => {
+                removed.insert(old_position, field);
+            }
+            Some(new_position) => {
+                if new_position != old_position {
+                    changes.push(Change::FieldMoved {
+                        container: container.to_string(),
+                        name: field.name.clone(),
+                        old: old_position,
+                        new: new_position,
+                    });
+                }
+                if field.value != new[new_position].value {
+                    changes.push(Change::FieldTypeChanged {
+                        container: container.to_string(),
+                        name: field.name.clone(),
+                        old: field.value.clone(),
+                        new: new[new_position].value.clone(),
+                    });
+                }
+            }
+        }
+    }
+    for (new_position, field) in new.iter().enumerate() {
+        if position(old, &field.name).is_some() {
+            continue;
+        }
+        // A field that disappeared from the same position with the same type was renamed.
+        match removed.get(&new_position) {
+            Some(old_field) if old_field.value == field.value => {
+                changes.push(Change::FieldRenamed {
+                    container: container.to_string(),
+                    position: new_position,
+                    old: old_field.name.clone(),
+                    new: field.name.clone(),
+                });
+                removed.remove(&new_position);
+            }
+            _ => changes.push(Change::FieldAdded {
+                container: container.to_string(),
+                position: new_position,
+                name: field.name.clone(),
+                format: field.value.clone(),
+                trailing: new_position >= old.len(),
+            }),
+        }
+    }
+    for (old_position, field) in removed {
+        changes.push(Change::FieldRemoved {
+            container: container.to_string(),
+            position: old_position,
+            name: field.name.clone(),
+        });
+    }
+}
+
+fn diff_variants(
+    container: &str,
+    old: &BTreeMap<u32, Named<VariantFormat>>,
+    new: &BTreeMap<u32, Named<VariantFormat>>,
+    changes: &mut Vec<Change>,
+) {
+    let index = |variants: &BTreeMap<u32, Named<VariantFormat>>, name: &str| {
+        variants
+            .iter()
+            .find(|(_, variant)| variant.name == name)
+            .map(|(index, _)| *index)
+    };
+    let last_old_index = old.keys().next_back().copied();
+    let mut removed = BTreeMap::new();
+    for (old_index, variant) in old {
+        match index(new, &variant.name) {
+            None => {
+                removed.insert(*old_index, variant);
+            }
+            Some(new_index) => {
+                if new_index != *old_index {
+                    changes.push(Change::VariantIndexChanged {
+                        container: container.to_string(),
+                        name: variant.name.clone(),
+                        old: *old_index,
+                        new: new_index,
+                    });
+                }
+                if variant.value != new[&new_index].value {
+                    changes.push(Change::VariantPayloadChanged {
+                        container: container.to_string(),
+                        name: variant.name.clone(),
+                        old: variant.value.clone(),
+                        new: new[&new_index].value.clone(),
+                    });
+                }
+            }
+        }
+    }
+    for (new_index, variant) in new {
+        if index(old, &variant.name).is_some() {
+            continue;
+        }
+        // A variant that disappeared from the same tag with the same payload was renamed.
+        match removed.get(new_index) {
+            Some(old_variant) if old_variant.value == variant.value => {
+                changes.push(Change::VariantRenamed {
+                    container: container.to_string(),
+                    index: *new_index,
+                    old: old_variant.name.clone(),
+                    new: variant.name.clone(),
+                });
+                removed.remove(new_index);
+            }
+            _ => changes.push(Change::VariantAdded {
+                container: container.to_string(),
+                index: *new_index,
+                name: variant.name.clone(),
+                appended: last_old_index.is_none_or(|last| *new_index > last),
+            }),
+        }
+    }
+    for (old_index, variant) in removed {
+        changes.push(Change::VariantRemoved {
+            container: container.to_string(),
+            index: old_index,
+            name: variant.name.clone(),
+        });
+    }
+}
+
+fn container_kind(container: &ContainerFormat) -> &'static str {
+    match container {
+        ContainerFormat::UnitStruct => "a unit struct",
+        ContainerFormat::NewTypeStruct(_) => "a newtype struct",
+        ContainerFormat::TupleStruct(_) => "a tuple struct",
+        ContainerFormat::Struct(_) => "a struct",
+        ContainerFormat::Enum(_) => "an enum",
+    }
+}
+
+/// Renders a format with Rust-like syntax, e.g. `Option<Vec<InnerInstructions>>`.
+pub fn describe(format: &Format) -> String {
+    match format {
+        Format::Variable(_) => "?".to_string(),
+        Format::TypeName(name) => name.clone(),
+        Format::Unit => "()".to_string(),
+        Format::Bool => "bool".to_string(),
+        Format::I8 => "i8".to_string(),
+        Format::I16 => "i16".to_string(),
+        Format::I32 => "i32".to_string(),
+        Format::I64 => "i64".to_string(),
+        Format::I128 => "i128".to_string(),
+        Format::U8 => "u8".to_string(),
+        Format::U16 => "u16".to_string(),
+        Format::U32 => "u32".to_string(),
+        Format::U64 => "u64".to_string(),
+        Format::U128 => "u128".to_string(),
+        Format::F32 => "f32".to_string(),
+        Format::F64 => "f64".to_string(),
+        Format::Char => "char".to_string(),
+        Format::Str => "String".to_string(),
+        Format::Bytes => "Bytes".to_string(),
+        Format::Option(format) => format!("Option<{}>", describe(format)),
+        Format::Seq(format) => format!("Vec<{}>", describe(format)),
+        Format::Map { key, value } => format!("Map<{}, {}>", describe(key), describe(value)),
+        Format::Tuple(formats) => format!("({})", describe_all(formats)),
+        Format::TupleArray { content, size } => format!("[{}; {}]", describe(content), size),
+    }
+}
+
+fn describe_variant(variant: &VariantFormat) -> String {
+    match variant {
+        VariantFormat::Variable(_) => "?".to_string(),
+        VariantFormat::Unit => "unit".to_string(),
+        VariantFormat::NewType(format) => format!("({})", describe(format)),
+        VariantFormat::Tuple(formats) => format!("({})", describe_all(formats)),
+        VariantFormat::Struct(fields) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, describe(&field.value)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+    }
+}
+
+fn describe_all(formats: &[Format]) -> String {
+    let formats: Vec<String> = formats.iter().map(describe).collect();
+    formats.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, value: Format) -> Named<Format> {
+        Named {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    fn unit_enum(names: &[&str]) -> ContainerFormat {
+        let variants = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let variant = Named {
+                    name: name.to_string(),
+                    value: VariantFormat::Unit,
+                };
+                (index as u32, variant)
+            })
+            .collect();
+        ContainerFormat::Enum(variants)
+    }
+
+    fn registry(containers: Vec<(&str, ContainerFormat)>) -> Registry {
+        containers
+            .into_iter()
+            .map(|(name, container)| (name.to_string(), container))
+            .collect()
+    }
+
+    #[test]
+    fn identical_registries_have_no_changes() {
+        let old = registry(vec![("Error", unit_enum(&["A", "B"]))]);
+        assert_eq!(diff(&old, &old.clone()), vec![]);
+    }
+
+    #[test]
+    fn variant_renamed_with_the_same_tag() {
+        let old = registry(vec![("Error", unit_enum(&["A", "B"]))]);
+        let new = registry(vec![("Error", unit_enum(&["A", "C"]))]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![Change::VariantRenamed {
+                container: "Error".to_string(),
+                index: 1,
+                old: "B".to_string(),
+                new: "C".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn variant_moved_by_an_insertion() {
+        let old = registry(vec![("Error", unit_enum(&["A", "B"]))]);
+        let new = registry(vec![("Error", unit_enum(&["A", "C", "B"]))]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Change::VariantIndexChanged {
+                    container: "Error".to_string(),
+                    name: "B".to_string(),
+                    old: 1,
+                    new: 2,
+                },
+                Change::VariantAdded {
+                    container: "Error".to_string(),
+                    index: 1,
+                    name: "C".to_string(),
+                    appended: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn variant_appended() {
+        let old = registry(vec![("Error", unit_enum(&["A"]))]);
+        let new = registry(vec![("Error", unit_enum(&["A", "B"]))]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![Change::VariantAdded {
+                container: "Error".to_string(),
+                index: 1,
+                name: "B".to_string(),
+                appended: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn trailing_and_inner_fields_added() {
+        let old = registry(vec![(
+            "Meta",
+            ContainerFormat::Struct(vec![field("fee", Format::U64)]),
+        )]);
+        let new = registry(vec![(
+            "Meta",
+            ContainerFormat::Struct(vec![
+                field("status", Format::Unit),
+                field("fee", Format::U64),
+                field("inner", Format::Option(Box::new(Format::U8))),
+            ]),
+        )]);
+        let changes = diff(&old, &new);
+        assert!(changes.contains(&Change::FieldMoved {
+            container: "Meta".to_string(),
+            name: "fee".to_string(),
+            old: 0,
+            new: 1,
+        }));
+        assert!(changes.contains(&Change::FieldAdded {
+            container: "Meta".to_string(),
+            position: 0,
+            name: "status".to_string(),
+            format: Format::Unit,
+            trailing: false,
+        }));
+        assert!(changes.contains(&Change::FieldAdded {
+            container: "Meta".to_string(),
+            position: 2,
+            name: "inner".to_string(),
+            format: Format::Option(Box::new(Format::U8)),
+            trailing: true,
+        }));
+        assert_eq!(changes.len(), 3);
+    }
+
+    #[test]
+    fn field_renamed_and_retyped() {
+        let old = registry(vec![(
+            "Meta",
+            ContainerFormat::Struct(vec![field("fee", Format::U64), field("a", Format::U8)]),
+        )]);
+        let new = registry(vec![(
+            "Meta",
+            ContainerFormat::Struct(vec![field("fee", Format::U32), field("b", Format::U8)]),
+        )]);
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Change::FieldTypeChanged {
+                    container: "Meta".to_string(),
+                    name: "fee".to_string(),
+                    old: Format::U64,
+                    new: Format::U32,
+                },
+                Change::FieldRenamed {
+                    container: "Meta".to_string(),
+                    position: 1,
+                    old: "a".to_string(),
+                    new: "b".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn containers_added_removed_and_reshaped() {
+        let old = registry(vec![
+            ("Gone", ContainerFormat::UnitStruct),
+            ("Shape", ContainerFormat::UnitStruct),
+        ]);
+        let new = registry(vec![
+            ("Added", ContainerFormat::UnitStruct),
+            ("Shape", unit_enum(&["A"])),
+        ]);
+        let changes: Vec<String> = diff(&old, &new).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            changes,
+            vec!["+ Added", "- Gone", "~ Shape: a unit struct became an enum"]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use crate::legacy::{self, limits};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("io error on {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("vector {0} does not decode back to its value")]
+    RoundTrip(String),
+    #[error("duplicate vector {0}")]
+    Duplicate(String),
+    #[error("{0}")]
+    Legacy(#[from] legacy::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Directory, relative to the repository root, where the vectors are checked in.
+pub const DIR: &str = "vectors";
+
+/// Golden vectors for one version: bincode bytes and the JSON they decode to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Fixtures {
+    pub commit: String,
+    /// The type every vector is an instance of.
+    pub root: String,
+    pub vectors: Vec<Vector>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Vector {
+    pub name: String,
+    /// Hex-encoded bincode bytes.
+    pub bincode: String,
+    /// The value as encoded by serde_json.
+    pub json: Value,
+    /// The deepest nesting of the value, as `Limits::max_depth` counts it.
+    pub depth: usize,
+}
+
+impl Fixtures {
+    pub fn new(commit: &str, root: &str) -> Self {
+        Self {
+            commit: commit.to_string(),
+            root: root.to_string(),
+            vectors: Vec::new(),
+        }
+    }
+
+    /// Records `value` under `name`, after checking that its bytes decode back to it.
+    pub fn push<T: Serialize + DeserializeOwned>(&mut self, name: &str, value: &T) -> Result<()> {
+        if self.vectors.iter().any(|vector| vector.name == name) {
+            return Err(Error::Duplicate(name.to_string()));
+        }
+        let bytes = bincode::serialize(value)?;
+        let json = serde_json::to_value(value)?;
+        let decoded: T = bincode::deserialize(&bytes)?;
+        if serde_json::to_value(&decoded)? != json {
+            return Err(Error::RoundTrip(name.to_string()));
+        }
+        let depth = limits::depth::<T>(&bytes, &limits::Limits::default())?;
+        self.vectors.push(Vector {
+            name: name.to_string(),
+            bincode: hex::encode(bytes),
+            json,
+            depth,
+        });
+        Ok(())
+    }
+
+    /// The conventional location of the vectors for `commit`.
+    pub fn path(commit: &str) -> PathBuf {
+        Path::new(DIR).join(format!("{}.json", commit))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| Error::Io(path.to_path_buf(), e))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// The contents of its file at `Self::path`, as the generators write it.
+    pub fn contents(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+}
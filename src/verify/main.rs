@@ -0,0 +1,30 @@
+use demo_serde_txmeta::generate;
+use std::path::Path;
+
+// Regenerates every version in memory and compares it with the checked-in files,
+// byte for byte apart from line endings. Generated Go is checked in unformatted,
+// so running gofmt over it shows up here as a difference.
+fn main() {
+    let artifacts = match generate::Options::from_args(std::env::args().skip(1))
+        .and_then(|options| generate::all(&options))
+    {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    let diffs = generate::verify(Path::new("."), &artifacts);
+    for diff in &diffs {
+        print!("{}", diff);
+    }
+    if !diffs.is_empty() {
+        eprintln!(
+            "{} of {} generated files are out of date, run the generators to update them",
+            diffs.len(),
+            artifacts.len()
+        );
+        std::process::exit(1);
+    }
+    println!("{} generated files are up to date", artifacts.len());
+}